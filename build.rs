@@ -0,0 +1,83 @@
+/// Generates the solution registry of the main binary.
+///
+/// With the `linked-solutions` feature, every solution in `src/bin` is included as a module, and its `SOLUTION`
+/// constant is collected into `SOLUTIONS`. Without it, `SOLUTIONS` is empty, so that a solution that does not compile
+/// only breaks its own binary. See `src/template/registry.rs`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let is_linked = env::var_os("CARGO_FEATURE_LINKED_SOLUTIONS").is_some();
+
+    // solutions are named after their day (`01.rs`), or their year and day (`2022-01.rs`).
+    let mut solutions: Vec<(Option<String>, String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    let stem = path.file_stem()?.to_str()?;
+                    let (year, day) = match stem.split_once('-') {
+                        Some((year, day)) => (Some(year), day),
+                        None => (None, stem),
+                    };
+                    let is_year = year.is_none_or(|year| {
+                        year.len() == 4 && matches!(year.parse::<u16>(), Ok(2015..))
+                    });
+                    let is_day = day.len() == 2 && matches!(day.parse::<u8>(), Ok(1..=25));
+                    (is_year && is_day).then(|| {
+                        (
+                            year.map(str::to_string),
+                            day.to_string(),
+                            path.to_str().unwrap().to_string(),
+                        )
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if !is_linked {
+        solutions.clear();
+    }
+
+    solutions.sort();
+
+    let module_name = |year: &Option<String>, day: &str| match year {
+        Some(year) => format!("y{year}_day_{day}"),
+        None => format!("day_{day}"),
+    };
+
+    let mut out = String::new();
+
+    for (year, day, path) in &solutions {
+        // NOTE: solution tests already run as part of each solution binary.
+        out.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {path:?}]\nmod {};\n",
+            module_name(year, day)
+        ));
+    }
+
+    out.push_str("\n#[cfg(not(test))]\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for (year, day, _) in &solutions {
+        let module = module_name(year, day);
+        match year {
+            // the year of a solution is derived from its binary name, which is not available in the main binary.
+            Some(year) => out.push_str(&format!(
+                "    advent_of_code::template::registry::Solution {{ year: Some(advent_of_code::template::Year::__new_unchecked({year})), ..{module}::SOLUTION }},\n"
+            )),
+            None => out.push_str(&format!("    {module}::SOLUTION,\n")),
+        }
+    }
+    out.push_str("];\n");
+
+    out.push_str("\n#[cfg(test)]\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
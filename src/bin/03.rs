@@ -10,10 +10,12 @@ use std::{
     str::FromStr,
 };
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, parse = get_schematic);
 
 type Coord = (isize, isize);
 
+type Schematic = Vec<Vec<(SchematicEntry, usize)>>;
+
 fn get_coord_neighbours(coord: Coord) -> BTreeSet<Coord> {
     BTreeSet::from([
         // Same line
@@ -31,7 +33,7 @@ fn get_coord_neighbours(coord: Coord) -> BTreeSet<Coord> {
 }
 
 #[derive(Debug)]
-enum SchematicEntry {
+pub enum SchematicEntry {
     PartNumber(u32),
     Symbol(char),
     Period,
@@ -65,7 +67,7 @@ fn parse_schematic_line(input: &str) -> IResult<&str, Vec<(SchematicEntry, usize
     many1(parse_schematic_entry)(input)
 }
 
-fn get_schematic(input: &str) -> Vec<Vec<(SchematicEntry, usize)>> {
+pub fn get_schematic(input: &str) -> Schematic {
     input
         .lines()
         .map(parse_schematic_line)
@@ -74,19 +76,17 @@ fn get_schematic(input: &str) -> Vec<Vec<(SchematicEntry, usize)>> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let schematic = get_schematic(input);
-
+pub fn part_one(schematic: &Schematic) -> Option<u32> {
     let mut number_coords = BTreeMap::<Coord, (u32, usize)>::new();
     let mut symbol_coords = BTreeSet::<Coord>::new();
 
-    for (i, line) in schematic.into_iter().enumerate() {
+    for (i, line) in schematic.iter().enumerate() {
         let i = i as isize;
         let mut j: isize = 0;
-        for (entry, width) in line {
+        for &(ref entry, width) in line {
             match entry {
                 SchematicEntry::PartNumber(num) => {
-                    number_coords.insert((i, j), (num, width));
+                    number_coords.insert((i, j), (*num, width));
                 }
                 SchematicEntry::Symbol(_) => {
                     symbol_coords.insert((i, j));
@@ -116,22 +116,20 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(total_touching)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let schematic = get_schematic(input);
-
+pub fn part_two(schematic: &Schematic) -> Option<u32> {
     let mut number_coords = BTreeMap::<Coord, (u32, usize)>::new();
     let mut asterisk_coords = BTreeMap::<Coord, Vec<u32>>::new();
 
-    for (i, line) in schematic.into_iter().enumerate() {
+    for (i, line) in schematic.iter().enumerate() {
         let i = i as isize;
         let mut j: isize = 0;
-        for (entry, width) in line {
+        for &(ref entry, width) in line {
             match entry {
                 SchematicEntry::PartNumber(num) => {
-                    number_coords.insert((i, j), (num, width));
+                    number_coords.insert((i, j), (*num, width));
                 }
                 SchematicEntry::Symbol(ch) => {
-                    if ch == '*' {
+                    if *ch == '*' {
                         asterisk_coords.insert((i, j), Vec::new());
                     }
                 }
@@ -179,13 +177,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&get_schematic(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(4361));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&get_schematic(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(467835));
     }
 }
@@ -10,10 +10,10 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, parse = parse_cards);
 
 #[derive(Debug, Clone)]
-struct Card {
+pub struct Card {
     num: usize,
     winning: Vec<u32>,
     present: Vec<u32>,
@@ -41,17 +41,19 @@ fn parse_card(input: &str) -> IResult<&str, Card> {
     ))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let cards: Vec<Card> = input
+pub fn parse_cards(input: &str) -> Vec<Card> {
+    input
         .lines()
         .map(parse_card)
         .map(|r| r.expect("puzzle input to parse").1)
-        .collect();
+        .collect()
+}
 
+pub fn part_one(cards: &[Card]) -> Option<u32> {
     let mut total_points = 0;
 
     for card in cards {
-        let winning_set = BTreeSet::from_iter(card.winning);
+        let winning_set = BTreeSet::from_iter(&card.winning);
         let num_winning = card
             .present
             .iter()
@@ -66,13 +68,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(total_points)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards: Vec<Card> = input
-        .lines()
-        .map(parse_card)
-        .map(|r| r.expect("puzzle input to parse").1)
-        .collect();
-
+pub fn part_two(cards: &[Card]) -> Option<u32> {
     let mut current_cards: Vec<usize> = cards.iter().map(|c| c.num).collect();
 
     let mut card_total = 0;
@@ -106,13 +102,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_cards(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_cards(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(30));
     }
 }
@@ -8,9 +8,9 @@ use nom::{
 };
 use std::str::FromStr;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse_input);
 
-struct Range {
+pub struct Range {
     dest_start: usize,
     source_start: usize,
     source_end: usize,
@@ -61,7 +61,9 @@ fn parse_seeds(input: &str) -> IResult<&str, Vec<usize>> {
     )(input)
 }
 
-fn parse_input(input: &str) -> (Vec<usize>, Vec<Vec<Range>>) {
+type Almanac = (Vec<usize>, Vec<Vec<Range>>);
+
+pub fn parse_input(input: &str) -> Almanac {
     let sections: Vec<&str> = input.split("\n\n").collect();
     let seed_section = &sections[0];
     let map_sections = &sections[1..];
@@ -81,10 +83,11 @@ fn parse_input(input: &str) -> (Vec<usize>, Vec<Vec<Range>>) {
     (seeds, maps)
 }
 
-fn get_min_location(seeds: Vec<usize>, maps: Vec<Vec<Range>>) -> Option<u32> {
+fn get_min_location(seeds: &[usize], maps: &[Vec<Range>]) -> Option<u32> {
     let mut mapped_seeds = Vec::new();
-    for mut seed in seeds {
-        for map in &maps {
+    for &seed in seeds {
+        let mut seed = seed;
+        for map in maps {
             for range in map {
                 let (mapped, new_seed) = range.check_map(seed);
                 seed = new_seed;
@@ -99,21 +102,60 @@ fn get_min_location(seeds: Vec<usize>, maps: Vec<Vec<Range>>) -> Option<u32> {
     mapped_seeds.into_iter().min().map(|x| x as u32)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (seeds, maps) = parse_input(input);
+pub fn part_one((seeds, maps): &Almanac) -> Option<u32> {
     get_min_location(seeds, maps)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (seeds, maps) = parse_input(input);
+/// Map seed ranges of the form `(start, end)` through a map, splitting them where they only partly overlap a range.
+fn map_seed_ranges(seed_ranges: Vec<(usize, usize)>, map: &[Range]) -> Vec<(usize, usize)> {
+    let mut mapped = Vec::new();
+    let mut unmapped = seed_ranges;
+
+    for range in map {
+        let mut remaining = Vec::new();
+        for (start, end) in unmapped {
+            let overlap_start = start.max(range.source_start);
+            let overlap_end = end.min(range.source_end);
+
+            if overlap_start >= overlap_end {
+                remaining.push((start, end));
+                continue;
+            }
 
+            mapped.push((
+                range.dest_start + (overlap_start - range.source_start),
+                range.dest_start + (overlap_end - range.source_start),
+            ));
+            if start < overlap_start {
+                remaining.push((start, overlap_start));
+            }
+            if overlap_end < end {
+                remaining.push((overlap_end, end));
+            }
+        }
+        unmapped = remaining;
+    }
+
+    mapped.extend(unmapped);
+    mapped
+}
+
+pub fn part_two((seeds, maps): &Almanac) -> Option<u32> {
     // Expand out the seeds
-    let mut new_seeds = Vec::new();
-    for pair in seeds.chunks_exact(2) {
-        let start = pair[0];
+    let mut seed_ranges: Vec<(usize, usize)> = seeds
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[0] + pair[1]))
+        .collect();
+
+    for map in maps {
+        seed_ranges = map_seed_ranges(seed_ranges, map);
     }
 
-    get_min_location(seeds, maps)
+    seed_ranges
+        .into_iter()
+        .map(|(start, _)| start)
+        .min()
+        .map(|x| x as u32)
 }
 
 #[cfg(test)]
@@ -122,13 +164,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
+        assert_eq!(result, Some(46));
     }
 }
@@ -1,13 +1,24 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, answers, dashboard, download, example, read, run_day, scaffold, solve, stress, time,
+};
+use advent_of_code::template::Year;
 use args::{parse, AppArguments};
+use solutions::SOLUTIONS;
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+/// All solutions in `src/bin`, linked into this binary by the build script if the `linked-solutions` feature is
+/// enabled. Empty otherwise.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::{stress, time};
+    use advent_of_code::template::{compare, run_multi::Timeouts, Day, Year};
     use std::process;
 
     pub enum AppArguments {
@@ -17,62 +28,127 @@ mod args {
         Read {
             day: Day,
         },
+        Example {
+            day: Day,
+            overwrite: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            heap: bool,
             submit: Option<u8>,
+            watch: bool,
         },
         All {
             release: bool,
+            check: bool,
+            jobs: usize,
+            timeouts: Timeouts,
         },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
+        Time(time::Options),
+        Stress(stress::Options),
+        RunDay {
+            day: Day,
+            time: bool,
         },
+        Answers,
+        Dashboard,
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    fn parse_timeouts(args: &mut pico_args::Arguments) -> Result<Timeouts, pico_args::Error> {
+        Ok(Timeouts::from_secs(
+            args.opt_value_from_str("--timeout")?,
+            args.opt_value_from_str("--part-timeout")?,
+        ))
+    }
+
+    /// Parse the command and the year it applies to, which every command accepts via `--year`.
+    pub fn parse() -> Result<(AppArguments, Option<Year>), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        let year = args.opt_value_from_str("--year")?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                check: args.contains("--check"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                timeouts: parse_timeouts(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+                let cpu = args.contains("--cpu");
+                let compare = args.contains("--compare") || baseline.is_some();
+                let threshold = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(compare::DEFAULT_THRESHOLD);
+                let save_baseline = args.opt_value_from_str("--save-baseline")?;
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let timeouts = parse_timeouts(&mut args)?;
+                let scaling = args.contains("--scaling");
 
-                AppArguments::Time {
+                AppArguments::Time(time::Options {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
-                }
+                    compare: compare.then_some(time::Comparison {
+                        threshold,
+                        baseline,
+                        cpu,
+                    }),
+                    save_baseline,
+                    jobs,
+                    timeouts,
+                    scaling,
+                })
             }
+            Some("stress") => AppArguments::Stress(stress::Options {
+                runs: args.opt_value_from_str("--runs")?.unwrap_or(100),
+                max_size: args.opt_value_from_str("--size")?.unwrap_or(100),
+                seed: args.opt_value_from_str("--seed")?,
+                day: args.free_from_str()?,
+            }),
+            Some("run-day") => AppArguments::RunDay {
+                day: args.free_from_str()?,
+                time: args.contains("--time"),
+            },
+            Some("answers") => AppArguments::Answers,
+            Some("dashboard") => AppArguments::Dashboard,
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("example") => AppArguments::Example {
+                day: args.free_from_str()?,
+                overwrite: args.contains("--overwrite"),
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                heap: args.contains("--heap"),
+                watch: args.contains("--watch"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -91,7 +167,7 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((app_args, year))
     }
 }
 
@@ -101,40 +177,66 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        Ok((args, year)) => {
+            // the configured year keeps the flat layout, other years selected via `--year` get their own directory.
+            let is_other_year = year.is_some() && year != Year::current();
+            if let Some(year) = year {
+                year.set_current();
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+
+            match args {
+                AppArguments::All {
+                    release,
+                    check,
+                    jobs,
+                    timeouts,
+                } => all::handle(release, check, jobs, timeouts, SOLUTIONS),
+                AppArguments::Time(options) => time::handle(options, SOLUTIONS),
+                AppArguments::Stress(options) => stress::handle(options, SOLUTIONS),
+                AppArguments::RunDay { day, time } => run_day::handle(day, time, SOLUTIONS),
+                AppArguments::Answers => answers::handle(),
+                AppArguments::Dashboard => dashboard::handle(SOLUTIONS),
+                AppArguments::Download { day } => download::handle(day),
+                AppArguments::Read { day } => read::handle(day),
+                AppArguments::Example { day, overwrite } => example::handle(day, overwrite),
+                AppArguments::Scaffold {
+                    day,
+                    download,
+                    overwrite,
+                    template,
+                } => scaffold::handle(
+                    day,
+                    overwrite,
+                    is_other_year,
+                    download,
+                    template.as_deref(),
+                ),
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    heap,
+                    submit,
+                    watch,
+                } => solve::handle(day, release, dhat, heap, submit, watch),
+                #[cfg(feature = "today")]
+                AppArguments::Today => {
+                    match Day::today() {
+                        Some(day) => {
+                            scaffold::handle(day, false, is_other_year, true, None);
+                            example::handle(day, false);
+                            read::handle(day)
+                        }
+                        None => {
+                            eprintln!(
+                                "`today` command can only be run between the 1st and \
+                                the 25th of december. Please use `scaffold` with a specific day."
+                            );
+                            process::exit(1)
+                        }
+                    };
+                }
             }
-        },
+        }
     };
 }
@@ -0,0 +1,257 @@
+/// Confirmed answers that solution results are checked against.
+///
+/// The manifest in `data/answers.json` maps days to the confirmed answers of their parts, e.g.
+/// `{ "01": { "1": "142", "2": "281" } }`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::PathBuf,
+    str::FromStr,
+    sync::OnceLock,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, Day, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// Answers are kept per year, see [`data_dir`].
+fn answers_path() -> PathBuf {
+    data_dir().join("answers.json")
+}
+
+/// Outcome of checking a result against the known answers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    /// There is no confirmed answer for this part yet.
+    Unknown,
+}
+
+/// Known answers per day and part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    data: BTreeMap<(Day, u8), String>,
+}
+
+impl Answers {
+    /// Rehydrate answers from the manifest. If not present, returns no answers.
+    pub fn read_from_file() -> Self {
+        let path = answers_path();
+        let s = match fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Answers::default(),
+            Err(e) => {
+                eprintln!("{e}");
+                return Answers::default();
+            }
+        };
+
+        Answers::try_from(s).unwrap_or_else(|e| {
+            eprintln!("Failed to read \"{}\": {e}", path.display());
+            Answers::default()
+        })
+    }
+
+    /// Dehydrate answers to the manifest.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(answers_path())?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: Day, part: u8, answer: String) {
+        self.data.insert((day, part), answer);
+    }
+
+    /// Check the result of a part. A missing result is wrong if the answer is known.
+    pub fn verify(&self, day: Day, part: u8, result: Option<&str>) -> Verdict {
+        match (self.get(day, part), result) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Some(result)) if expected == result.trim() => Verdict::Correct,
+            (Some(_), _) => Verdict::Wrong,
+        }
+    }
+
+    /// Format a short mark for a result, e.g. `✔` or `✘ expected 42`. Unknown results are not marked.
+    pub fn mark(&self, day: Day, part: u8, result: Option<&str>) -> String {
+        match self.verify(day, part, result) {
+            Verdict::Correct => format!(" {ANSI_GREEN}✔{ANSI_RESET}"),
+            Verdict::Wrong => format!(
+                " {ANSI_RED}✘ expected {}{ANSI_RESET}",
+                self.get(day, part).unwrap_or_default()
+            ),
+            Verdict::Unknown => String::new(),
+        }
+    }
+}
+
+/// Extract the confirmed answers from a puzzle description downloaded via `cargo download`, in order of the parts.
+/// Once a star is earned, the description contains a line like "Your puzzle answer was `142`."
+pub fn parse_puzzle_answers(markdown: &str) -> Vec<String> {
+    const PREFIX: &str = "Your puzzle answer was";
+
+    markdown
+        .lines()
+        .filter_map(|line| {
+            let rest = line[line.find(PREFIX)? + PREFIX.len()..].trim();
+
+            let answer = match rest.strip_prefix('`') {
+                Some(quoted) => &quoted[..quoted.find('`')?],
+                None => rest.strip_suffix('.').unwrap_or(rest),
+            };
+
+            let answer = answer.trim();
+            (!answer.is_empty()).then(|| answer.to_string())
+        })
+        .collect()
+}
+
+/// Answers of the manifest, read once per process.
+pub fn known() -> &'static Answers {
+    static ANSWERS: OnceLock<Answers> = OnceLock::new();
+    ANSWERS.get_or_init(Answers::read_from_file)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut days: HashMap<String, HashMap<String, JsonValue>> = HashMap::new();
+
+        for ((day, part), answer) in &value.data {
+            days.entry(day.to_string())
+                .or_default()
+                .insert(part.to_string(), JsonValue::String(answer.clone()));
+        }
+
+        JsonValue::Object(
+            days.into_iter()
+                .map(|(day, parts)| (day, JsonValue::Object(parts)))
+                .collect(),
+        )
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut answers = Answers::default();
+
+        for (day, parts) in days {
+            let day = Day::from_str(day).or(Err(format!("expected `{day}` to be a day.")))?;
+
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("expected answers of day {day} to be an object."))?;
+
+            for (part, answer) in parts {
+                let part = match part.parse::<u8>() {
+                    Ok(part @ 1..=2) => part,
+                    _ => return Err(format!("expected `{part}` of day {day} to be a part.")),
+                };
+
+                let answer = answer.get::<String>().ok_or(format!(
+                    "expected answer to day {day}, part {part} to be a string."
+                ))?;
+
+                answers.insert(day, part, answer.clone());
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_puzzle_answers, Answers, Verdict};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    fn get_mock_answers() -> Answers {
+        Answers::try_from(
+            r#"{ "01": { "1": "142", "2": "281" }, "05": { "1": "35" } }"#.to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn reads_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.get(day!(1), 1), Some("142"));
+        assert_eq!(answers.get(day!(1), 2), Some("281"));
+        assert_eq!(answers.get(day!(5), 1), Some("35"));
+        assert_eq!(answers.get(day!(5), 2), None);
+    }
+
+    #[test]
+    fn verifies_results() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.verify(day!(1), 1, Some("142")), Verdict::Correct);
+        assert_eq!(answers.verify(day!(1), 2, Some("280")), Verdict::Wrong);
+        assert_eq!(answers.verify(day!(1), 2, None), Verdict::Wrong);
+        assert_eq!(answers.verify(day!(5), 2, Some("46")), Verdict::Unknown);
+        assert_eq!(answers.verify(day!(2), 1, None), Verdict::Unknown);
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let answers = get_mock_answers();
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn parses_puzzle_answers() {
+        let markdown = "\
+## --- Day 1: Trebuchet?! ---
+
+Consider your entire calibration document. What is the sum of all of the calibration values?
+
+Your puzzle answer was `54388`.
+
+## --- Part Two ---
+
+What is the sum of all of the calibration values?
+
+Your puzzle answer was `53515`.
+
+Both parts of this puzzle are complete! They provide two gold stars: \\*\\*
+";
+        assert_eq!(parse_puzzle_answers(markdown), vec!["54388", "53515"]);
+    }
+
+    #[test]
+    fn parses_unquoted_puzzle_answers() {
+        let markdown =
+            "Your puzzle answer was ABCDEFGH.\n\nThe first half of this puzzle is complete!";
+        assert_eq!(parse_puzzle_answers(markdown), vec!["ABCDEFGH"]);
+    }
+
+    #[test]
+    fn parses_unsolved_puzzles() {
+        assert_eq!(
+            parse_puzzle_answers("To begin, get your puzzle input.").len(),
+            0
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_parts() {
+        Answers::try_from(r#"{ "01": { "3": "142" } }"#.to_string()).unwrap();
+    }
+}
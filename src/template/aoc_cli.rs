@@ -1,10 +1,11 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    io::{self, Write},
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{data_dir, Day, Year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -72,33 +73,40 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
+/// Submit an answer. The response is printed and also returned in the `stdout` of the output, so it can be parsed.
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    let output = Command::new("aoc")
+        .args(&args)
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    let _ = io::stdout().write_all(&output.stdout);
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+pub fn get_input_path(day: Day) -> String {
+    format!("{}/inputs/{day}.txt", data_dir().display())
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+pub fn get_puzzle_path(day: Day) -> String {
+    format!("{}/puzzles/{day}.md", data_dir().display())
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = Year::current() {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
@@ -0,0 +1,430 @@
+/// Built-in client for the Advent of Code website.
+///
+/// Reads the session cookie from the same places as aoc-cli: the `ADVENT_OF_CODE_SESSION` environment variable or
+/// the `.adventofcode.session` file in the home or config directory. The base URL can be changed with the
+/// `AOC_BASE_URL` environment variable, e.g. to point the client to a local mock server.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{submissions::SubmissionVerdict, Day, Year};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found.
+    MissingSession,
+    /// `AOC_YEAR` is not set.
+    MissingYear,
+    /// The server responded with an error status.
+    Status {
+        status: u16,
+        url: String,
+    },
+    /// The request did not reach the server, or its response could not be read.
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or create ~/.adventofcode.session."
+            ),
+            AocClientError::MissingYear => write!(f, "AOC_YEAR is not set."),
+            AocClientError::Status { status: 400, url } => write!(
+                f,
+                "request to {url} was rejected (400). Is the session cookie still valid?"
+            ),
+            AocClientError::Status { status: 404, url } => {
+                write!(f, "{url} was not found (404). Is the puzzle unlocked yet?")
+            }
+            AocClientError::Status { status, url } => {
+                write!(f, "request to {url} failed with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => AocClientError::Status {
+                status,
+                url: response.get_url().to_string(),
+            },
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// Response of the server to a submitted answer.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SubmitResponse {
+    pub verdict: SubmissionVerdict,
+    /// The message of the server, as plain text.
+    pub message: String,
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: Year,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+
+        AocClient {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Build a client from the environment. Fails with [`AocClientError::MissingSession`] if no session cookie is set up.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+
+        let year = Year::current().ok_or(AocClientError::MissingYear)?;
+
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(AocClient::new(&base_url, &session, year))
+    }
+
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&format!("{}/input", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// The puzzle description, converted to markdown. Includes the answers of solved parts.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(day))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(puzzle_markdown(&response.into_string()?))
+    }
+
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitResponse, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let html = response.into_string()?;
+        let message = extract_blocks(&html, "<article")
+            .iter()
+            .map(|block| to_markdown(block))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Ok(SubmitResponse {
+            verdict: SubmissionVerdict::parse(&message),
+            message,
+        })
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|home| home.join(".adventofcode.session")),
+        config.map(|config| config.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .filter(|session| !session.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Convert a puzzle page to markdown, keeping the puzzle descriptions and the answers of solved parts.
+fn puzzle_markdown(html: &str) -> String {
+    let blocks = extract_blocks(html, "<article");
+    let answers = extract_blocks(html, "<p>Your puzzle answer was");
+
+    // NOTE: every answer follows the description of its part.
+    let mut markdown: Vec<String> = vec![];
+    for (i, block) in blocks.iter().enumerate() {
+        markdown.push(to_markdown(block));
+        if let Some(answer) = answers.get(i) {
+            markdown.push(to_markdown(answer));
+        }
+    }
+
+    markdown.join("\n\n") + "\n"
+}
+
+/// All elements starting with `start`, e.g. `<article`, including their tags.
+fn extract_blocks<'a>(html: &'a str, start: &str) -> Vec<&'a str> {
+    let tag = start[1..].split([' ', '>']).next().unwrap_or_default();
+    let end = format!("</{tag}>");
+
+    let mut blocks = vec![];
+    let mut rest = html;
+
+    while let Some(from) = rest.find(start) {
+        let Some(to) = rest[from..].find(&end) else {
+            break;
+        };
+        let to = from + to + end.len();
+        blocks.push(&rest[from..to]);
+        rest = &rest[to..];
+    }
+
+    blocks
+}
+
+/// Minimal HTML to markdown conversion for the markup used in puzzle descriptions.
+fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while let Some(open) = rest.find('<') {
+        out.push_str(&rest[..open]);
+
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[open + 1..open + close];
+        let name = tag
+            .trim_start_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+        let is_closing = tag.starts_with('/');
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            _ => {}
+        }
+
+        rest = &rest[open + close + 1..];
+    }
+
+    out.push_str(rest);
+
+    let out = decode_entities(&out);
+
+    // collapse runs of blank lines left by nested blocks.
+    let mut collapsed = String::new();
+    for line in out.trim().lines() {
+        if line.trim().is_empty() && collapsed.ends_with("\n\n") {
+            continue;
+        }
+        collapsed.push_str(line.trim_end());
+        collapsed.push('\n');
+    }
+
+    collapsed.trim_end().to_string()
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_markdown, AocClient, AocClientError};
+    use crate::{
+        day,
+        template::{submissions::SubmissionVerdict, Year},
+    };
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    const PUZZLE_HTML: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with <code>a &lt; b</code>.</p>
+<pre><code>1abc2
+<em>pqr3stu8vwx</em>
+</code></pre>
+<ul><li>one</li><li>two</li></ul>
+</article>
+<p>Your puzzle answer was <code>54388</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Again.</p></article>
+<p>Your puzzle answer was <code>53515</code>.</p>
+<p class="day-success">Both parts of this puzzle are complete!</p>
+</main></body></html>"#;
+
+    /// Serve one canned response per request, handing each request to the returned receiver.
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+
+                let mut body_buf = vec![0; content_length];
+                reader.read_exact(&mut body_buf).unwrap();
+                request.push_str(&String::from_utf8(body_buf).unwrap());
+                // tests that ignore the request drop the receiver.
+                let _ = tx.send(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let markdown = puzzle_markdown(PUZZLE_HTML);
+        assert_eq!(
+            markdown,
+            "## --- Day 1: Trebuchet?! ---\n\nSomething is *wrong* with `a < b`.\n\n```\n1abc2\npqr3stu8vwx\n```\n\n- one\n- two\n\nYour puzzle answer was `54388`.\n\n## --- Part Two ---\n\nAgain.\n\nYour puzzle answer was `53515`.\n"
+        );
+    }
+
+    #[test]
+    fn fetches_inputs() {
+        let (url, requests) = mock_server(vec![(200, "1abc2\n")]);
+        let client = AocClient::new(&url, "secret", Year::new(2023).unwrap());
+
+        assert_eq!(client.input(day!(1)).unwrap(), "1abc2\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input "));
+        assert!(request.contains("Cookie: session=secret"));
+    }
+
+    #[test]
+    fn fetches_puzzles() {
+        let (url, _) = mock_server(vec![(200, PUZZLE_HTML)]);
+        let client = AocClient::new(&url, "secret", Year::new(2023).unwrap());
+
+        let puzzle = client.puzzle(day!(1)).unwrap();
+        assert!(puzzle.starts_with("## --- Day 1: Trebuchet?! ---"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, requests) = mock_server(vec![(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>",
+        )]);
+        let client = AocClient::new(&url, "secret", Year::new(2023).unwrap());
+
+        let response = client.submit(day!(5), 2, "42").unwrap();
+        assert_eq!(response.verdict, SubmissionVerdict::TooLow);
+        assert_eq!(
+            response.message,
+            "That's not the right answer; your answer is too low."
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/5/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_status_errors() {
+        let (url, _) = mock_server(vec![(404, "Not found")]);
+        let client = AocClient::new(&url, "secret", Year::new(2023).unwrap());
+
+        assert!(matches!(
+            client.input(day!(24)),
+            Err(AocClientError::Status { status: 404, .. })
+        ));
+    }
+}
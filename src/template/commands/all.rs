@@ -1,5 +1,60 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::env;
+use std::process::{self, Command};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::run_multi::{run_multi, RunOptions, Timeouts};
+use crate::template::{all_days, registry::Solution};
+
+/// Run all solutions. With `check`, exits with a non-zero status if any answer differs from the known answers.
+pub fn handle(
+    is_release: bool,
+    check: bool,
+    jobs: usize,
+    timeouts: Timeouts,
+    solutions: &[Solution],
+) {
+    let options = RunOptions {
+        is_release,
+        is_timed: false,
+        jobs,
+        timeouts,
+        solutions,
+    };
+
+    if !solutions.is_empty() && !options.use_registry() {
+        rerun_in_profile(is_release);
+    }
+
+    let summary = run_multi(&all_days().collect(), &options);
+
+    if check && summary.has_wrong_answers() {
+        process::exit(1);
+    }
+}
+
+/// Rebuild the main binary in the profile that the solutions were requested in and run the same command with it.
+/// The aliases always build the main binary in release mode, which could otherwise only run the solutions of a
+/// debug `cargo all` by invoking `cargo` once per day.
+fn rerun_in_profile(is_release: bool) -> ! {
+    let mut cmd_args = vec!["run".to_string(), "--quiet".to_string()];
+
+    if is_release {
+        cmd_args.push("--release".to_string());
+    }
+
+    let mut features = vec!["linked-solutions"];
+    if cfg!(feature = "heap-stats") {
+        features.push("heap-stats");
+    }
+    cmd_args.extend(["--features".to_string(), features.join(",")]);
+
+    cmd_args.push("--".to_string());
+    cmd_args.extend(env::args().skip(1));
+
+    match Command::new("cargo").args(&cmd_args).status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,41 @@
+use std::{fs, process};
+
+use crate::template::answers::{parse_puzzle_answers, Answers};
+use crate::template::{all_days, aoc_cli};
+
+/// Fill the known answers from the "Your puzzle answer was ..." lines of all downloaded puzzle descriptions.
+pub fn handle() {
+    let mut answers = Answers::read_from_file();
+    let mut harvested = 0;
+
+    for day in all_days() {
+        let Ok(markdown) = fs::read_to_string(aoc_cli::get_puzzle_path(day)) else {
+            continue;
+        };
+
+        for (part, answer) in (1..=2).zip(parse_puzzle_answers(&markdown)) {
+            match answers.get(day, part) {
+                Some(known) if known == answer => continue,
+                Some(known) => {
+                    println!("Day {day} Part {part}: replacing \"{known}\" with \"{answer}\".");
+                }
+                None => println!("Day {day} Part {part}: {answer}"),
+            }
+
+            answers.insert(day, part, answer);
+            harvested += 1;
+        }
+    }
+
+    if harvested == 0 {
+        println!("No new answers found.");
+        return;
+    }
+
+    if let Err(e) = answers.store_file() {
+        eprintln!("Failed to store answers: {e}");
+        process::exit(1);
+    }
+
+    println!("🎄 Stored {harvested} new answers.");
+}
@@ -0,0 +1,102 @@
+use std::io::{self, Read, Write};
+
+use crate::template::commands::{solve, time};
+use crate::template::dashboard::{load, move_selection, parse_key, render, Key, RawMode};
+use crate::template::registry::Solution;
+use crate::template::run_multi::Timeouts;
+
+const ANSI_ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h\x1b[?25l";
+const ANSI_LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+const HELP: &str = "[←↑↓→/hjkl] select  [r] run  [t] time  [1]/[2] submit  [q] quit";
+
+/// Show the state of every day, and run, time or submit the selected day.
+/// Prints the calendar once if stdin is not an interactive terminal.
+pub fn handle(solutions: &[Solution]) {
+    let mut days = load();
+    let mut selected = 0;
+
+    let Some(raw_mode) = RawMode::enable() else {
+        print!("{}", render(&days, selected));
+        return;
+    };
+
+    print!("{ANSI_ENTER_ALTERNATE_SCREEN}");
+    let mut raw_mode = Some(raw_mode);
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}{}\n{HELP}", render(&days, selected));
+        io::stdout().flush().unwrap();
+
+        let Some(key) = read_key() else {
+            break;
+        };
+
+        let day = days[selected].day;
+
+        // actions print like the regular commands, so leave raw mode while they run.
+        let action: Box<dyn Fn()> = match key {
+            Key::Char('q') => break,
+            Key::Char('r') => Box::new(move || solve::handle(day, true, false, false, None, false)),
+            Key::Char('t') => Box::new(move || {
+                time::handle(
+                    time::Options {
+                        day: Some(day),
+                        all: false,
+                        store: true,
+                        compare: None,
+                        save_baseline: None,
+                        jobs: 1,
+                        timeouts: Timeouts::default(),
+                        scaling: false,
+                    },
+                    solutions,
+                );
+            }),
+            Key::Char(part @ ('1' | '2')) => {
+                let part = part as u8 - b'0';
+                Box::new(move || solve::handle(day, true, false, false, Some(part), false))
+            }
+            key => {
+                selected = move_selection(selected, key, days.len());
+                continue;
+            }
+        };
+
+        drop(raw_mode.take());
+        print!("{ANSI_CLEAR_SCREEN}");
+        io::stdout().flush().unwrap();
+
+        action();
+
+        raw_mode = RawMode::enable();
+        print!("\nPress any key to return to the dashboard.");
+        io::stdout().flush().unwrap();
+        if read_key().is_none() {
+            break;
+        }
+
+        days = load();
+    }
+
+    drop(raw_mode);
+    print!("{ANSI_LEAVE_ALTERNATE_SCREEN}");
+    io::stdout().flush().unwrap();
+}
+
+/// Wait for a key press. Returns `None` once stdin is closed.
+fn read_key() -> Option<Key> {
+    let mut buffer = [0; 8];
+
+    loop {
+        match io::stdin().read(&mut buffer) {
+            Ok(0) | Err(_) => return None,
+            Ok(n) => {
+                if let Some(key) = parse_key(&buffer[..n]) {
+                    return Some(key);
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,40 @@
+use crate::template::aoc_client::{AocClient, AocClientError};
 use crate::template::{aoc_cli, Day};
-use std::process;
+use std::{fs, process};
 
 pub fn handle(day: Day) {
+    match AocClient::from_env() {
+        Ok(client) => {
+            if let Err(e) = download(&client, day) {
+                eprintln!("failed to download day {day}: {e}");
+                process::exit(1);
+            }
+        }
+        // without a session cookie, leave authentication to aoc-cli.
+        Err(AocClientError::MissingSession) => download_via_aoc_cli(day),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn download(client: &AocClient, day: Day) -> Result<(), AocClientError> {
+    let input_path = aoc_cli::get_input_path(day);
+    let puzzle_path = aoc_cli::get_puzzle_path(day);
+
+    let puzzle = client.puzzle(day)?;
+    let input = client.input(day)?;
+
+    fs::write(&puzzle_path, puzzle)?;
+    fs::write(&input_path, input)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+fn download_via_aoc_cli(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -0,0 +1,89 @@
+use std::{fs, process};
+
+use crate::template::examples::{fill_test, parse_puzzle_examples};
+use crate::template::{aoc_cli, bin_name, data_dir, Day};
+
+/// Write the example of a downloaded puzzle description to `data/examples` and fill its expected answers into the
+/// scaffolded tests. Existing examples are kept unless `overwrite` is set.
+pub fn handle(day: Day, overwrite: bool) {
+    let puzzle_path = aoc_cli::get_puzzle_path(day);
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("Could not read \"{puzzle_path}\". Try running `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let examples = parse_puzzle_examples(&markdown);
+
+    if examples.iter().all(|e| e.input.is_none()) {
+        println!("No example found in \"{puzzle_path}\".");
+        return;
+    }
+
+    let module_path = format!("src/bin/{}.rs", bin_name(day));
+    let mut module = fs::read_to_string(&module_path).ok();
+
+    for (part, example) in (1..=2).zip(&examples) {
+        // the second part only has an input if its example differs from the first one.
+        let part_file = (part == 2 && example.input.is_some()).then_some(part);
+
+        if let Some(input) = &example.input {
+            let example_path = match part_file {
+                Some(part) => format!("{}/examples/{day}-{part}.txt", data_dir().display()),
+                None => format!("{}/examples/{day}.txt", data_dir().display()),
+            };
+
+            println!("Example for part {part}:\n```\n{input}```");
+            write_example(&example_path, input, overwrite);
+        }
+
+        let Some(answer) = &example.answer else {
+            continue;
+        };
+
+        println!("Expected answer for part {part}: {answer}");
+
+        if answer.parse::<i64>().is_err() {
+            println!("Not a number, fill it into the test of part {part} by hand.");
+            continue;
+        }
+
+        let test = if part == 1 {
+            "test_part_one"
+        } else {
+            "test_part_two"
+        };
+
+        if let Some(filled) = module
+            .as_deref()
+            .and_then(|source| fill_test(source, test, answer, part_file))
+        {
+            println!("Filled expected answer into `{test}`.");
+            module = Some(filled);
+        }
+    }
+
+    if let Some(module) = module {
+        if let Err(e) = fs::write(&module_path, module) {
+            eprintln!("Failed to update module file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn write_example(path: &str, input: &str, overwrite: bool) {
+    let is_empty = fs::read_to_string(path).map_or(true, |s| s.trim().is_empty());
+
+    if !is_empty && !overwrite {
+        println!("Keeping existing example file \"{path}\". Pass `--overwrite` to replace it.");
+        return;
+    }
+
+    match fs::write(path, input) {
+        Ok(()) => println!("Wrote example to \"{path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,6 +1,11 @@
 pub mod all;
+pub mod answers;
+pub mod dashboard;
 pub mod download;
+pub mod example;
 pub mod read;
+pub mod run_day;
 pub mod scaffold;
 pub mod solve;
+pub mod stress;
 pub mod time;
@@ -1,8 +1,27 @@
 use std::process;
 
+use crate::template::aoc_client::{AocClient, AocClientError};
 use crate::template::{aoc_cli, Day};
 
 pub fn handle(day: Day) {
+    match AocClient::from_env() {
+        Ok(client) => match client.puzzle(day) {
+            Ok(puzzle) => println!("{puzzle}"),
+            Err(e) => {
+                eprintln!("failed to read day {day}: {e}");
+                process::exit(1);
+            }
+        },
+        // without a session cookie, leave authentication to aoc-cli.
+        Err(AocClientError::MissingSession) => read_via_aoc_cli(day),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn read_via_aoc_cli(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -0,0 +1,16 @@
+use std::process;
+
+use crate::template::{read_file, registry, registry::Solution, runner, Day};
+
+/// Run a solution that is linked into the main binary. Used by `all` and `time` to run solutions in a child process.
+pub fn handle(day: Day, is_timed: bool, solutions: &[Solution]) {
+    let Some(solution) = registry::find(solutions, day) else {
+        eprintln!(
+            "Solution for day {day} is not linked into this binary. Build it with `--features linked-solutions`."
+        );
+        process::exit(1);
+    };
+
+    runner::set_timed(is_timed);
+    (solution.run)(&read_file("inputs", day));
+}
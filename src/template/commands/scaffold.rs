@@ -1,13 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::commands::download;
+use crate::template::templates::{self, parse_puzzle_title};
+use crate::template::{aoc_cli, bin_name, data_dir, Day, Year};
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -27,10 +26,61 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Create an empty data file. Files that already have content are kept, e.g. an input downloaded before scaffolding.
+fn create_data_file(path: &str, kind: &str) {
+    if fs::metadata(path).is_ok_and(|m| m.len() > 0) {
+        println!("Kept existing {kind} file \"{path}\"");
+        return;
+    }
+
+    match create_file(path) {
+        Ok(_) => {
+            println!("Created empty {kind} file \"{path}\"");
+        }
+        Err(e) => {
+            eprintln!("Failed to create {kind} file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Scaffold a day of the selected year from the template called `template`, or the default template. With `own_dir`,
+/// the year gets its own directory if it does not have one yet. With `download`, the input and puzzle are downloaded
+/// before the solution is created, so that the template can use the title of the puzzle.
+pub fn handle(day: Day, overwrite: bool, own_dir: bool, download: bool, template: Option<&str>) {
+    let template = match templates::load(template.unwrap_or(templates::DEFAULT_NAME)) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if own_dir {
+        create_year_dir();
+    }
+
+    if download {
+        download::handle(day);
+    }
+
+    let data_dir = data_dir();
+    let input_path = format!("{}/inputs/{day}.txt", data_dir.display());
+    let example_path = format!("{}/examples/{day}.txt", data_dir.display());
+    let module_path = format!("src/bin/{}.rs", bin_name(day));
+
+    // solutions of years with their own directory read their examples from it, regardless of the selected year.
+    let template = match Year::scoped() {
+        Some(_) => template.replace(
+            "read_file(\"examples\", DAY)",
+            "read_year_file(\"examples\", YEAR, DAY)",
+        ),
+        None => template,
+    };
+
+    let title = fs::read_to_string(aoc_cli::get_puzzle_path(day))
+        .ok()
+        .and_then(|markdown| parse_puzzle_title(&markdown));
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +90,9 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file
+        .write_all(templates::fill(&template, day, Year::current(), title.as_deref()).as_bytes())
+    {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -54,26 +102,33 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    create_data_file(&input_path, "input");
+    create_data_file(&example_path, "example");
+
+    println!("---");
+    match Year::scoped() {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+fn create_year_dir() {
+    let Some(year) = Year::current() else {
+        eprintln!("No year selected. Pass `--year` or set `AOC_YEAR`.");
+        process::exit(1);
+    };
+
+    if year.has_own_dir() {
+        return;
+    }
+
+    let data_dir = format!("data/{year}");
+    for folder in ["inputs", "examples", "puzzles"] {
+        if let Err(e) = fs::create_dir_all(format!("{data_dir}/{folder}")) {
+            eprintln!("Failed to create directory for {year}: {e}");
             process::exit(1);
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("Created directory \"{data_dir}\" for {year}");
 }
@@ -1,9 +1,46 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::watch::{self, parse_test_output};
+use crate::template::{bin_name, Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    heap: bool,
+    submit_part: Option<u8>,
+    watch: bool,
+) {
+    if !watch {
+        run(day, release, dhat, heap, submit_part);
+        return;
+    }
+
+    if submit_part.is_some() {
+        eprintln!("`--submit` can not be combined with `--watch`.");
+        process::exit(1);
+    }
+
+    watch::watch(
+        || watch::watched_files(day),
+        || {
+            print!("{ANSI_CLEAR_SCREEN}");
+            println!(
+                "{ANSI_BOLD}Day {day}{ANSI_RESET}, watching for changes. Press Ctrl-C to stop.\n"
+            );
+
+            if run_tests(day, release) {
+                println!();
+                run(day, release, dhat, heap, None);
+            }
+        },
+    );
+}
+
+fn run(day: Day, release: bool, dhat: bool, heap: bool, submit_part: Option<u8>) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name(day)];
 
     if dhat {
         cmd_args.extend([
@@ -12,8 +49,14 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if release {
+            cmd_args.push("--release".to_string());
+        }
+
+        if heap {
+            cmd_args.extend(["--features".to_string(), "heap-stats".to_string()]);
+        }
     }
 
     cmd_args.push("--".to_string());
@@ -32,3 +75,62 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Run the tests of a day and print a line per test. Prints the compiler output instead if the solution does not
+/// build. Returns whether the solution built.
+fn run_tests(day: Day, release: bool) -> bool {
+    let mut cmd_args = vec![
+        "test".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        bin_name(day),
+        "--color".to_string(),
+        "always".to_string(),
+    ];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    // `--quiet` hides the build progress, but also switches the test harness to its terse format.
+    cmd_args.extend([
+        "--".to_string(),
+        "--format".to_string(),
+        "pretty".to_string(),
+    ]);
+
+    let output = match Command::new("cargo")
+        .args(&cmd_args)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            return false;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let results = parse_test_output(&stdout);
+
+    if !output.status.success() && results.is_empty() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        return false;
+    }
+
+    for result in results {
+        let name = result.name.strip_prefix("tests::").unwrap_or(&result.name);
+
+        if result.passed {
+            println!("{ANSI_GREEN}✔{ANSI_RESET} {name}");
+        } else {
+            println!("{ANSI_RED}✖{ANSI_RESET} {name}");
+            for line in result.output {
+                println!("    {line}");
+            }
+        }
+    }
+
+    true
+}
@@ -0,0 +1,101 @@
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::template::registry::{self, Solution};
+use crate::template::stress::{check, shrink};
+use crate::template::{Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// Runs of the solution to spend on shrinking a failing input.
+const SHRINK_ATTEMPTS: usize = 1000;
+
+pub struct Options {
+    pub day: Day,
+    /// Number of inputs to generate.
+    pub runs: usize,
+    /// Size of the largest input. Inputs grow from size 1 to this size over the runs.
+    pub max_size: usize,
+    /// Seed of the first input, the following inputs count up from there. Random if not set.
+    pub seed: Option<u64>,
+}
+
+/// Check a solution against generated inputs of growing size. Stops at the first failure, shrinks the failing input
+/// and prints it.
+pub fn handle(options: Options, solutions: &[Solution]) {
+    let day = options.day;
+
+    let Some(solution) = registry::find(solutions, day) else {
+        eprintln!(
+            "Solution for day {day} is not linked into this binary. Build it with `--features linked-solutions`."
+        );
+        process::exit(1);
+    };
+
+    let Some(generator) = solution.generator else {
+        eprintln!("Day {day} has no input generator. Register one with `solution!({day}, generator = generate_input)`.");
+        process::exit(1);
+    };
+
+    let seed = options.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+
+    println!(
+        "Stress testing day {day} with {} inputs of size 1 to {} (seed {seed})...",
+        options.runs, options.max_size
+    );
+
+    for run in 0..options.runs {
+        let size = 1 + run * options.max_size / options.runs;
+        let input_seed = seed.wrapping_add(run as u64);
+        let input = generator(size, input_seed);
+
+        let Some(failure) = check(solution, &input) else {
+            continue;
+        };
+
+        println!(
+            "{ANSI_RED}✖{ANSI_RESET} Input {} (size {size}, seed {input_seed}) failed. {failure}",
+            run + 1
+        );
+
+        // the same seed often fails at smaller sizes as well, which gives shrinking a head start.
+        let (input, failure) = (1..size)
+            .find_map(|size| {
+                let input = generator(size, input_seed);
+                check(solution, &input)
+                    .filter(|f| f.is_like(&failure))
+                    .map(|f| (input, f))
+            })
+            .unwrap_or((input, failure));
+
+        println!("Shrinking...");
+
+        let mut last_failure = failure;
+        let shrunk = shrink(
+            &input,
+            |candidate| match check(solution, candidate) {
+                Some(failure) if failure.is_like(&last_failure) => {
+                    last_failure = failure;
+                    true
+                }
+                _ => false,
+            },
+            SHRINK_ATTEMPTS,
+        );
+
+        println!(
+            "{ANSI_BOLD}Smallest failing input{ANSI_RESET} ({} lines):\n```\n{}\n```",
+            shrunk.lines().count(),
+            shrunk.trim_end()
+        );
+        println!("{last_failure}");
+        process::exit(1);
+    }
+
+    println!(
+        "{ANSI_GREEN}✔{ANSI_RESET} No failures in {} inputs.",
+        options.runs
+    );
+}
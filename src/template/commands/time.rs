@@ -1,16 +1,90 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::compare::{compare, print_deltas};
+use crate::template::registry::{self, Solution};
+use crate::template::run_multi::{run_multi, RunOptions, Timeouts};
+use crate::template::timings::{is_valid_baseline_name, Timings};
+use crate::template::{all_days, readme_benchmarks, scaling, Day};
+
+pub struct Options {
+    pub day: Option<Day>,
+    pub all: bool,
+    pub store: bool,
+    pub compare: Option<Comparison>,
+    /// Name of a baseline to save the fresh results to.
+    pub save_baseline: Option<String>,
+    pub jobs: usize,
+    pub timeouts: Timeouts,
+    /// Benchmark how the solution of `day` scales with the size of its input instead.
+    pub scaling: bool,
+}
+
+/// Compare fresh results to stored timings, failing if a step regresses by more than `threshold` percent.
+pub struct Comparison {
+    pub threshold: f64,
+    /// Name of a baseline to compare to. Compares to the main timings if not set.
+    pub baseline: Option<String>,
+    /// Compare thread CPU time instead of wall-clock time where both runs recorded it.
+    pub cpu: bool,
+}
+
+pub fn handle(options: Options, solutions: &[Solution]) {
+    if options.scaling {
+        let Some(day) = options.day else {
+            eprintln!("Pass the day to benchmark, e.g. `cargo time 1 --scaling`.");
+            process::exit(1);
+        };
+
+        let Some(solution) = registry::find(solutions, day) else {
+            eprintln!(
+                "Solution for day {day} is not linked into this binary. Build it with `--features linked-solutions`."
+            );
+            process::exit(1);
+        };
+
+        scaling::run(solution);
+        return;
+    }
+
+    let baseline_names = options.save_baseline.iter().chain(
+        options
+            .compare
+            .iter()
+            .filter_map(|comparison| comparison.baseline.as_ref()),
+    );
+
+    for name in baseline_names {
+        if !is_valid_baseline_name(name) {
+            eprintln!("Invalid baseline name \"{name}\". Use letters, digits, `-` and `_` only.");
+            process::exit(1);
+        }
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let reference_timings = options
+        .compare
+        .as_ref()
+        .map(|comparison| match &comparison.baseline {
+            Some(name) => Timings::read_baseline(name).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            }),
+            None => stored_timings.clone(),
+        });
+
+    let days_to_run = options.day.map_or_else(
         || {
-            if run_all {
+            if options.all {
                 all_days().collect()
+            } else if let Some(reference_timings) = &reference_timings {
+                // when comparing, re-run every day that has timings to compare to.
+                reference_timings
+                    .latest()
+                    .data
+                    .iter()
+                    .map(|t| t.day)
+                    .collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,14 +95,48 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run_options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        jobs: options.jobs,
+        timeouts: options.timeouts,
+        solutions,
+    };
+
+    let timings = run_multi(&days_to_run, &run_options).timings.stamp();
+
+    let has_regressions = options
+        .compare
+        .as_ref()
+        .zip(reference_timings.as_ref())
+        .is_some_and(|(comparison, reference_timings)| {
+            let deltas = compare(reference_timings, &timings, comparison.cpu);
+            print_deltas(&deltas, comparison.threshold);
+            deltas.iter().any(|d| d.is_regression(comparison.threshold))
+        });
+
+    if let Some(name) = &options.save_baseline {
+        let baseline = Timings::read_baseline(name)
+            .unwrap_or_default()
+            .merge(&timings);
 
-    if store {
+        println!();
+        match baseline.store_baseline(name) {
+            Ok(()) => {
+                println!("Saved baseline \"{name}\".");
+            }
+            Err(e) => {
+                eprintln!("Failed to save baseline \"{name}\": {e}");
+            }
+        }
+    }
+
+    if options.store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(&merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +145,8 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regressions {
+        process::exit(1);
+    }
 }
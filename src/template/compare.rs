@@ -0,0 +1,271 @@
+/// Compares fresh benchmark results against stored timings.
+use crate::template::{
+    report::Step,
+    stats::parse_duration,
+    timings::{Timing, Timings},
+    Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+};
+
+pub const DEFAULT_THRESHOLD: f64 = 10_f64;
+
+/// Change of the mean execution time of a single step between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub step: Step,
+    /// Whether thread CPU time was compared instead of wall-clock time.
+    pub is_cpu: bool,
+    pub old_nanos: f64,
+    /// `None` if the step failed, timed out or did not produce an answer in the new run.
+    pub new_nanos: Option<f64>,
+}
+
+impl Delta {
+    /// Relative change in percent. Positive values mean the step got slower. `None` if the step has no new timing.
+    pub fn percent(&self) -> Option<f64> {
+        let new_nanos = self.new_nanos?;
+
+        if self.old_nanos == 0_f64 {
+            return Some(0_f64);
+        }
+        Some((new_nanos - self.old_nanos) / self.old_nanos * 100_f64)
+    }
+
+    /// Whether the step got slower by more than `threshold` percent, or no longer has a timing at all.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent().is_none_or(|percent| percent > threshold)
+    }
+
+    fn label(&self) -> String {
+        let step = self.step.to_string();
+
+        if self.is_cpu {
+            format!("{step} (cpu)")
+        } else {
+            step
+        }
+    }
+}
+
+/// Compute deltas for all steps that have a timing in `old`. Steps that are missing in `new`, e.g. because they
+/// failed or timed out, are included without a new timing.
+/// Fresh results are compared to the most recent stored run of their day. Wall-clock time is compared, unless
+/// `use_cpu` is set and both runs recorded thread CPU time, which is less affected by other load on the machine.
+pub fn compare(old: &Timings, new: &Timings, use_cpu: bool) -> Vec<Delta> {
+    let mut deltas = vec![];
+
+    for new_timing in &new.data {
+        let Some(old_timing) = old.latest_for(new_timing.day) else {
+            continue;
+        };
+
+        for step in [Step::Parse, Step::Part(1), Step::Part(2)] {
+            let cpu = (
+                step_cpu_nanos(old_timing, step),
+                step_cpu_nanos(new_timing, step),
+            );
+            let wall = (step_nanos(old_timing, step), step_nanos(new_timing, step));
+
+            let is_cpu = use_cpu && matches!(cpu, (Some(_), Some(_)));
+
+            let (Some(old_nanos), new_nanos) = (if is_cpu { cpu } else { wall }) else {
+                continue;
+            };
+
+            deltas.push(Delta {
+                day: new_timing.day,
+                step,
+                is_cpu,
+                old_nanos,
+                new_nanos,
+            });
+        }
+    }
+
+    deltas
+}
+
+/// Print deltas, highlighting steps that changed by more than `threshold` percent.
+pub fn print_deltas(deltas: &[Delta], threshold: f64) {
+    let clock = if deltas.iter().any(|delta| delta.is_cpu) {
+        "cpu time where recorded, else wall-clock time"
+    } else {
+        "wall-clock time"
+    };
+    println!("\n{ANSI_BOLD}Comparison{ANSI_RESET} ({clock}, threshold: {threshold}%)");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare against.");
+        return;
+    }
+
+    for delta in deltas {
+        let Some((new_nanos, percent)) = delta.new_nanos.zip(delta.percent()) else {
+            println!(
+                "Day {} {}: {} → {ANSI_RED}no result{ANSI_RESET}",
+                delta.day,
+                delta.label(),
+                format_nanos(delta.old_nanos),
+            );
+            continue;
+        };
+
+        let color = if delta.is_regression(threshold) {
+            ANSI_RED
+        } else if percent < -threshold {
+            ANSI_GREEN
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {} {}: {} → {} ({color}{percent:+.1}%{ANSI_RESET})",
+            delta.day,
+            delta.label(),
+            format_nanos(delta.old_nanos),
+            format_nanos(new_nanos),
+        );
+    }
+}
+
+/// Mean execution time of a step. Falls back to the formatted time for timings stored without stats.
+fn step_nanos(timing: &Timing, step: Step) -> Option<f64> {
+    let (time, stats) = match step {
+        Step::Parse => (&timing.parse, &timing.parse_stats),
+        Step::Part(1) => (&timing.part_1, &timing.part_1_stats),
+        Step::Part(2) => (&timing.part_2, &timing.part_2_stats),
+        Step::Part(_) => return None,
+    };
+
+    match stats {
+        Some(stats) => Some(stats.mean),
+        None => time.as_deref().and_then(parse_duration),
+    }
+}
+
+/// Mean thread CPU time of a step, if recorded.
+fn step_cpu_nanos(timing: &Timing, step: Step) -> Option<f64> {
+    let stats = match step {
+        Step::Parse => &timing.parse_cpu_stats,
+        Step::Part(1) => &timing.part_1_cpu_stats,
+        Step::Part(2) => &timing.part_2_cpu_stats,
+        Step::Part(_) => return None,
+    };
+
+    stats.as_ref().map(|stats| stats.mean)
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = std::time::Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, Delta};
+    use crate::{
+        day,
+        template::{
+            report::Step,
+            stats::Stats,
+            timings::{Timing, Timings},
+        },
+    };
+    use std::time::Duration;
+
+    fn get_mock_timing(part_1: &str, part_2_nanos: Option<u64>) -> Timing {
+        Timing {
+            part_1: Some(part_1.into()),
+            part_2: part_2_nanos.map(|n| format!("{:.1?}", Duration::from_nanos(n))),
+            part_2_stats: part_2_nanos.map(|n| Stats::from_samples(&[Duration::from_nanos(n)])),
+            ..Timing::new(day!(1))
+        }
+    }
+
+    #[test]
+    fn compares_timings() {
+        let old = Timings {
+            data: vec![get_mock_timing("1.0ms", Some(2_000_000))],
+        };
+        let new = Timings {
+            data: vec![get_mock_timing("1.5ms", Some(1_000_000))],
+        };
+
+        let deltas = compare(&old, &new, false);
+        assert_eq!(deltas.len(), 2);
+
+        assert_eq!(deltas[0].step, Step::Part(1));
+        assert_eq!(deltas[0].percent(), Some(50_f64));
+        assert_eq!(deltas[0].is_regression(10_f64), true);
+
+        assert_eq!(deltas[1].step, Step::Part(2));
+        assert_eq!(deltas[1].percent(), Some(-50_f64));
+        assert_eq!(deltas[1].is_regression(10_f64), false);
+    }
+
+    #[test]
+    fn compares_cpu_time_if_requested() {
+        let mut old = get_mock_timing("1.0ms", Some(2_000_000));
+        let mut new = get_mock_timing("1.0ms", Some(4_000_000));
+        old.part_2_cpu_stats = Some(Stats::from_samples(&[Duration::from_millis(1)]));
+        new.part_2_cpu_stats = Some(Stats::from_samples(&[Duration::from_millis(1)]));
+
+        let old = Timings { data: vec![old] };
+        let new = Timings { data: vec![new] };
+
+        let deltas = compare(&old, &new, false);
+        assert_eq!(deltas[1].is_cpu, false);
+        assert_eq!(deltas[1].percent(), Some(100_f64));
+
+        let deltas = compare(&old, &new, true);
+        assert_eq!(deltas[0].is_cpu, false);
+        assert_eq!(deltas[1].is_cpu, true);
+        assert_eq!(deltas[1].percent(), Some(0_f64));
+    }
+
+    #[test]
+    fn skips_missing_steps() {
+        let old = Timings {
+            data: vec![get_mock_timing("1.0ms", None)],
+        };
+        let new = Timings {
+            data: vec![get_mock_timing("1.0ms", Some(1_000_000))],
+        };
+
+        let deltas = compare(&old, &new, false);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].percent(), Some(0_f64));
+    }
+
+    #[test]
+    fn reports_missing_steps_as_regressions() {
+        let old = Timings {
+            data: vec![get_mock_timing("1.0ms", Some(1_000_000))],
+        };
+        let new = Timings {
+            data: vec![get_mock_timing("1.0ms", None)],
+        };
+
+        let deltas = compare(&old, &new, false);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[1].step, Step::Part(2));
+        assert_eq!(deltas[1].new_nanos, None);
+        assert_eq!(deltas[1].is_regression(10_f64), true);
+    }
+
+    #[test]
+    fn respects_threshold() {
+        let delta = Delta {
+            day: day!(1),
+            step: Step::Part(1),
+            is_cpu: false,
+            old_nanos: 100_f64,
+            new_nanos: Some(105_f64),
+        };
+        assert_eq!(delta.is_regression(10_f64), false);
+        assert_eq!(delta.is_regression(2_f64), true);
+    }
+}
@@ -0,0 +1,67 @@
+/// Per-thread CPU time, which is not affected by time the thread spends waiting to be scheduled.
+use std::time::Duration;
+
+/// CPU time consumed by the current thread so far. `None` on platforms without a per-thread CPU clock.
+#[cfg(target_os = "linux")]
+pub fn thread_cpu_time() -> Option<Duration> {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: `ts` is a valid, writable timespec.
+    let result = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut ts) };
+
+    #[allow(clippy::cast_sign_loss)]
+    (result == 0).then(|| Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+/// CPU time consumed by the current thread so far. `None` on platforms without a per-thread CPU clock.
+#[cfg(not(target_os = "linux"))]
+pub fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Measures the CPU time between its creation and a call to [`CpuTimer::elapsed`].
+pub struct CpuTimer {
+    start: Option<Duration>,
+}
+
+impl CpuTimer {
+    pub fn start() -> Self {
+        CpuTimer {
+            start: thread_cpu_time(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Option<Duration> {
+        Some(thread_cpu_time()?.saturating_sub(self.start?))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::CpuTimer;
+    use std::{hint::black_box, thread, time::Duration};
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn measures_busy_time() {
+        let timer = CpuTimer::start();
+        let mut x = 0_u64;
+        for i in 0..1_000_000 {
+            x = black_box(x.wrapping_add(i));
+        }
+        assert!(timer.elapsed().unwrap() > Duration::ZERO);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn ignores_sleep() {
+        let timer = CpuTimer::start();
+        thread::sleep(Duration::from_millis(50));
+        assert!(timer.elapsed().unwrap() < Duration::from_millis(25));
+    }
+}
@@ -0,0 +1,360 @@
+/// State of every day of the calendar, as shown by `cargo dashboard`.
+///
+/// Collects what is otherwise spread over `cargo all`, `cargo time` and the readme: whether a day is scaffolded and
+/// has its input, its confirmed answers and submissions, and its stored timings.
+use std::path::Path;
+use std::time::Duration;
+
+use crate::template::answers::Answers;
+use crate::template::aoc_cli::get_input_path;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::submissions::{SubmissionVerdict, Submissions};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_REVERSE: &str = "\x1b[7m";
+
+const COLUMNS: usize = 5;
+const CELL_WIDTH: usize = 15;
+
+/// State of a single part of a day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartState {
+    /// Confirmed answer, from `data/answers.json` or a correct submission.
+    pub answer: Option<String>,
+    /// Verdict of the latest submission.
+    pub verdict: Option<SubmissionVerdict>,
+    pub submissions: usize,
+}
+
+impl PartState {
+    fn is_solved(&self) -> bool {
+        self.answer.is_some()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DayState {
+    pub day: Day,
+    pub is_scaffolded: bool,
+    pub has_input: bool,
+    pub parts: [PartState; 2],
+    /// The latest stored timing of the day.
+    pub timing: Option<Timing>,
+}
+
+impl DayState {
+    fn stars(&self) -> String {
+        self.parts
+            .iter()
+            .map(|part| if part.is_solved() { '★' } else { '☆' })
+            .collect()
+    }
+
+    /// Short status of the day, e.g. its total run time.
+    fn status(&self) -> String {
+        if !self.is_scaffolded {
+            "not started".into()
+        } else if !self.has_input {
+            "no input".into()
+        } else {
+            self.timing
+                .as_ref()
+                .map_or("not timed".into(), |t| format_nanos(t.total_nanos))
+        }
+    }
+
+    /// Verdict of the latest submission of the first unsolved part, if any.
+    fn last_verdict(&self) -> Option<String> {
+        let (i, part) = self
+            .parts
+            .iter()
+            .enumerate()
+            .find(|(_, part)| !part.is_solved())?;
+
+        Some(format!("{}: {}", i + 1, part.verdict?))
+    }
+}
+
+/// Read the state of every day from the files of the selected year.
+pub fn load() -> Vec<DayState> {
+    let answers = Answers::read_from_file();
+    let submissions = Submissions::read_from_file();
+    let timings = Timings::read_from_file();
+
+    all_days()
+        .map(|day| DayState {
+            day,
+            is_scaffolded: Path::new(&get_path_for_bin(day)).exists(),
+            has_input: Path::new(&get_input_path(day)).exists(),
+            parts: [1, 2].map(|part| {
+                let submitted = submissions.get(day, part);
+
+                PartState {
+                    answer: answers.get(day, part).map(ToString::to_string).or_else(|| {
+                        submitted
+                            .iter()
+                            .find(|s| s.verdict == SubmissionVerdict::Correct)
+                            .map(|s| s.answer.clone())
+                    }),
+                    verdict: submitted.last().map(|s| s.verdict),
+                    submissions: submitted.len(),
+                }
+            }),
+            timing: timings.latest_for(day).cloned(),
+        })
+        .collect()
+}
+
+/// Render the calendar as a grid of days, followed by the details of the selected day.
+pub fn render(days: &[DayState], selected: usize) -> String {
+    let mut out = String::new();
+
+    let stars: usize = days
+        .iter()
+        .flat_map(|day| &day.parts)
+        .filter(|part| part.is_solved())
+        .count();
+    out.push_str(&format!(
+        "{ANSI_BOLD}Advent of Code{ANSI_RESET}  {stars}★\n\n"
+    ));
+
+    for (row, chunk) in days.chunks(COLUMNS).enumerate() {
+        let cells: Vec<[String; 3]> = chunk
+            .iter()
+            .enumerate()
+            .map(|(column, day)| render_cell(day, row * COLUMNS + column == selected))
+            .collect();
+
+        for line in 0..3 {
+            let line: Vec<&str> = cells.iter().map(|cell| cell[line].as_str()).collect();
+            out.push_str(&line.join(" "));
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+
+    if let Some(day) = days.get(selected) {
+        out.push_str(&render_details(day));
+    }
+
+    out
+}
+
+fn render_cell(day: &DayState, is_selected: bool) -> [String; 3] {
+    let title = format!(" {} {}", day.day, day.stars());
+    let status = format!(" {}", day.status());
+    let verdict = format!(" {}", day.last_verdict().unwrap_or_default());
+
+    let (title_color, status_color) = if !day.is_scaffolded {
+        (ANSI_DIM, ANSI_DIM)
+    } else if !day.has_input {
+        (ANSI_BOLD, ANSI_RED)
+    } else if day.parts.iter().all(PartState::is_solved) {
+        (ANSI_BOLD, ANSI_GREEN)
+    } else {
+        (ANSI_BOLD, "")
+    };
+
+    let highlight = if is_selected { ANSI_REVERSE } else { "" };
+
+    [
+        (title, title_color),
+        (status, status_color),
+        (verdict, ANSI_RED),
+    ]
+    .map(|(text, color)| format!("{highlight}{color}{text:<CELL_WIDTH$}{ANSI_RESET}"))
+}
+
+fn render_details(day: &DayState) -> String {
+    let check = |is_present: bool| if is_present { "✔" } else { "✘" };
+
+    let mut out = format!(
+        "{ANSI_BOLD}Day {}{ANSI_RESET}  solution {}  input {}\n",
+        day.day,
+        check(day.is_scaffolded),
+        check(day.has_input)
+    );
+
+    for (i, part) in day.parts.iter().enumerate() {
+        let answer = match (&part.answer, part.verdict) {
+            (Some(answer), _) => format!("{ANSI_GREEN}{answer}{ANSI_RESET}"),
+            (None, Some(verdict)) => format!(
+                "unsolved, {} submission(s), last {ANSI_RED}{verdict}{ANSI_RESET}",
+                part.submissions
+            ),
+            (None, None) => "unsolved".into(),
+        };
+
+        let time = day
+            .timing
+            .as_ref()
+            .and_then(|t| {
+                if i == 0 {
+                    t.part_1.clone()
+                } else {
+                    t.part_2.clone()
+                }
+            })
+            .map(|time| format!(" ({time})"))
+            .unwrap_or_default();
+
+        out.push_str(&format!("  Part {}: {answer}{time}\n", i + 1));
+    }
+
+    out
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/// Keys that the dashboard reacts to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Char(char),
+}
+
+/// Parse a key press from the bytes read from a terminal in raw mode. Arrow keys arrive as escape sequences.
+pub fn parse_key(bytes: &[u8]) -> Option<Key> {
+    match bytes {
+        [0x1b, b'[', b'A', ..] => Some(Key::Up),
+        [0x1b, b'[', b'B', ..] => Some(Key::Down),
+        [0x1b, b'[', b'C', ..] => Some(Key::Right),
+        [0x1b, b'[', b'D', ..] => Some(Key::Left),
+        [0x1b, ..] => None,
+        // Ctrl-C does not send a signal in raw mode.
+        [0x03, ..] => Some(Key::Char('q')),
+        [byte, ..] => Some(Key::Char(char::from(*byte))),
+        [] => None,
+    }
+}
+
+/// Move the selection within the grid of days.
+pub fn move_selection(selected: usize, key: Key, len: usize) -> usize {
+    match key {
+        Key::Up | Key::Char('k') => selected.checked_sub(COLUMNS).unwrap_or(selected),
+        Key::Down | Key::Char('j') if selected + COLUMNS < len => selected + COLUMNS,
+        Key::Left | Key::Char('h') => selected.saturating_sub(1),
+        Key::Right | Key::Char('l') if selected + 1 < len => selected + 1,
+        _ => selected,
+    }
+}
+
+/// Puts the terminal into raw mode, so that single key presses can be read without echoing them.
+/// Restores the previous mode when dropped.
+#[cfg(unix)]
+pub struct RawMode {
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    /// Returns `None` if stdin is not a terminal.
+    pub fn enable() -> Option<Self> {
+        // SAFETY: `termios` is plain old data, and is only used after `tcgetattr` filled it in.
+        let mut original: libc::termios = unsafe { std::mem::zeroed() };
+
+        // SAFETY: `original` is a valid, writable termios.
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+            return None;
+        }
+
+        let mut raw = original;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+
+        // SAFETY: `raw` is a valid termios.
+        (unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } == 0)
+            .then_some(RawMode { original })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // SAFETY: `original` is the valid termios that was read in `enable`.
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original) };
+    }
+}
+
+/// Raw mode is only supported on Unix-like systems.
+#[cfg(not(unix))]
+pub struct RawMode;
+
+#[cfg(not(unix))]
+impl RawMode {
+    pub fn enable() -> Option<Self> {
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{move_selection, parse_key, render, DayState, Key, PartState};
+    use crate::template::submissions::SubmissionVerdict;
+    use crate::template::all_days;
+
+    fn days() -> Vec<DayState> {
+        all_days()
+            .map(|day| DayState {
+                day,
+                is_scaffolded: false,
+                has_input: false,
+                parts: [PartState::default(), PartState::default()],
+                timing: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn renders_days() {
+        let mut days = days();
+        days[3].is_scaffolded = true;
+        days[3].has_input = true;
+        days[3].parts[0].answer = Some("13".into());
+        days[3].parts[1] = PartState {
+            answer: None,
+            verdict: Some(SubmissionVerdict::TooHigh),
+            submissions: 2,
+        };
+
+        let out = render(&days, 3);
+
+        assert!(out.contains("1★"));
+        assert!(out.contains(" 04 ★☆"));
+        assert!(out.contains(" not timed"));
+        assert!(out.contains(" 2: too high"));
+        assert!(out.contains(" 05 ☆☆"));
+        assert!(out.contains("Day 04"));
+        assert!(out.contains("unsolved, 2 submission(s), last \x1b[31mtoo high"));
+    }
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(parse_key(b"\x1b[A"), Some(Key::Up));
+        assert_eq!(parse_key(b"\x1b[D"), Some(Key::Left));
+        assert_eq!(parse_key(b"t"), Some(Key::Char('t')));
+        assert_eq!(parse_key(b"\x03"), Some(Key::Char('q')));
+        assert_eq!(parse_key(b"\x1b"), None);
+    }
+
+    #[test]
+    fn moves_within_the_grid() {
+        assert_eq!(move_selection(0, Key::Up, 25), 0);
+        assert_eq!(move_selection(0, Key::Down, 25), 5);
+        assert_eq!(move_selection(22, Key::Down, 25), 22);
+        assert_eq!(move_selection(24, Key::Right, 25), 24);
+        assert_eq!(move_selection(6, Key::Char('h'), 25), 5);
+        assert_eq!(move_selection(20, Key::Char('k'), 25), 15);
+    }
+}
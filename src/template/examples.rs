@@ -0,0 +1,271 @@
+//! Extracts example inputs and their expected answers from downloaded puzzle descriptions.
+//!
+//! Puzzle descriptions introduce their example in a code block after a paragraph mentioning "example" and close with
+//! the expected answer in emphasised code, e.g. "In this example, adding these together produces `*142*`."
+
+/// Heading that starts the description of the second part.
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// The example of one part of a puzzle.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartExample {
+    /// Example input. `None` if the part reuses the example of the previous part.
+    pub input: Option<String>,
+    /// Expected answer for the example.
+    pub answer: Option<String>,
+}
+
+/// Extract the examples of both parts from a puzzle description. Parts that are not unlocked yet are omitted.
+pub fn parse_puzzle_examples(markdown: &str) -> Vec<PartExample> {
+    let mut examples: Vec<PartExample> = vec![];
+
+    for (i, section) in split_parts(markdown).into_iter().enumerate() {
+        let blocks = code_blocks(&section);
+
+        let input = if i == 0 {
+            // the first part always comes with an example, even if it is not introduced as one.
+            blocks
+                .iter()
+                .find(|(intro, _)| is_example_intro(intro))
+                .or(blocks.first())
+                .map(|(_, block)| block.clone())
+        } else {
+            blocks
+                .iter()
+                .find(|(intro, _)| is_example_intro(intro))
+                .map(|(_, block)| block.clone())
+                .filter(|block| examples.iter().all(|e| e.input.as_ref() != Some(block)))
+        };
+
+        let answer = section
+            .lines()
+            .filter(|line| !line.contains("Your puzzle answer was"))
+            .flat_map(emphasised_code)
+            .last();
+
+        examples.push(PartExample { input, answer });
+    }
+
+    examples
+}
+
+/// Set the expected value of a scaffolded test, e.g. `test_part_one`, if it still expects `None`.
+/// With `part_file`, the test reads its example from a part-specific file, e.g. `01-2.txt`.
+/// Returns `None` if the test can not be found or was already filled in.
+pub fn fill_test(source: &str, test: &str, answer: &str, part_file: Option<u8>) -> Option<String> {
+    let start = source.find(&format!("fn {test}()"))?;
+    let end = start + source[start..].find("\n    }\n")?;
+    let body = &source[start..end];
+
+    if !body.contains("assert_eq!(result, None);") {
+        return None;
+    }
+
+    let mut body = body.replace(
+        "assert_eq!(result, None);",
+        &format!("assert_eq!(result, Some({answer}));"),
+    );
+
+    if let Some(part) = part_file {
+        body = body
+            .replace(
+                "read_file(\"examples\", DAY)",
+                &format!("read_file_part(\"examples\", DAY, {part})"),
+            )
+            .replace(
+                "read_year_file(\"examples\", YEAR, DAY)",
+                &format!("read_year_file_part(\"examples\", YEAR, DAY, {part})"),
+            );
+    }
+
+    Some(format!("{}{body}{}", &source[..start], &source[end..]))
+}
+
+/// Split a description into the sections of its parts.
+fn split_parts(markdown: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+
+    for line in markdown.lines() {
+        if line.contains(PART_TWO_HEADING) {
+            parts.push(String::new());
+        }
+        let section = parts.last_mut().unwrap();
+        section.push_str(line);
+        section.push('\n');
+    }
+
+    parts
+}
+
+/// All fenced code blocks of a section, together with the paragraph preceding them.
+fn code_blocks(section: &str) -> Vec<(String, String)> {
+    let mut blocks = vec![];
+    let mut paragraph = String::new();
+    let mut block: Option<String> = None;
+
+    for line in section.lines() {
+        match (&mut block, line.trim_start().starts_with("```")) {
+            (None, true) => block = Some(String::new()),
+            (Some(_), true) => {
+                blocks.push((paragraph.clone(), block.take().unwrap()));
+            }
+            (Some(block), false) => {
+                block.push_str(line);
+                block.push('\n');
+            }
+            (None, false) if line.trim().is_empty() => {}
+            (None, false) => paragraph = line.to_string(),
+        }
+    }
+
+    blocks
+}
+
+fn is_example_intro(paragraph: &str) -> bool {
+    paragraph.to_lowercase().contains("example")
+}
+
+/// Emphasised code spans of a line, i.e. `` `*142*` `` or `` *`142`* ``.
+fn emphasised_code(line: &str) -> Vec<String> {
+    let mut spans = vec![];
+    let mut rest = line;
+
+    while let Some(open) = rest.find('`') {
+        let Some(len) = rest[open + 1..].find('`') else {
+            break;
+        };
+        let close = open + 1 + len;
+        let code = &rest[open + 1..close];
+
+        let is_wrapped = rest[..open].ends_with('*') && rest[close + 1..].starts_with('*');
+
+        let span = match code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+            Some(inner) => Some(inner),
+            None if is_wrapped => Some(code),
+            None => None,
+        };
+
+        if let Some(span) = span.map(str::trim).filter(|s| !s.is_empty()) {
+            spans.push(span.to_string());
+        }
+
+        rest = &rest[close + 1..];
+    }
+
+    spans
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasised_code, fill_test, parse_puzzle_examples, PartExample};
+
+    const PUZZLE: &str = "\
+## --- Day 1: Trebuchet?! ---
+
+The newly-improved calibration document consists of lines of text. Use `*` for wildcards:
+
+```
+a*b
+```
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values of these four lines are `*12*` and `*38*`. Adding these together produces `*50*`.
+
+Your puzzle answer was `54388`.
+
+## --- Part Two ---
+
+Equipped with this new information, you now need to find the real first and last digit on each line. For example:
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces *`281`*.
+";
+
+    const TEMPLATE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+    #[test]
+    fn parses_examples() {
+        assert_eq!(
+            parse_puzzle_examples(PUZZLE),
+            vec![
+                PartExample {
+                    input: Some("1abc2\npqr3stu8vwx\n".into()),
+                    answer: Some("50".into())
+                },
+                PartExample {
+                    input: Some("two1nine\neightwothree\n".into()),
+                    answer: Some("281".into())
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn reuses_examples_of_previous_parts() {
+        let puzzle = "\
+For example:
+
+```
+1
+2
+```
+
+This produces `*3*`.
+
+## --- Part Two ---
+
+Using the same example:
+
+```
+1
+2
+```
+
+This now produces `*2*`.
+";
+        let examples = parse_puzzle_examples(puzzle);
+        assert_eq!(examples[1].input, None);
+        assert_eq!(examples[1].answer, Some("2".into()));
+    }
+
+    #[test]
+    fn parses_locked_puzzles() {
+        let examples = parse_puzzle_examples("## --- Day 2 ---\n\nNothing to see.\n");
+        assert_eq!(examples, vec![PartExample::default()]);
+    }
+
+    #[test]
+    fn finds_emphasised_code() {
+        assert_eq!(
+            emphasised_code("`*a*` and *`b`*, but not `c` or *d*, then `*e*`."),
+            vec!["a", "b", "e"]
+        );
+    }
+
+    #[test]
+    fn fills_tests() {
+        let source = fill_test(TEMPLATE, "test_part_one", "142", None).unwrap();
+        let source = fill_test(&source, "test_part_two", "281", Some(2)).unwrap();
+
+        assert!(source.contains(
+            "let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));\n        assert_eq!(result, Some(142));"
+        ));
+        assert!(source.contains(
+            "let result = part_two(&advent_of_code::template::read_file_part(\"examples\", DAY, 2));\n        assert_eq!(result, Some(281));"
+        ));
+
+        assert_eq!(fill_test(&source, "test_part_one", "1", None), None);
+    }
+}
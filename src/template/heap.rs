@@ -0,0 +1,236 @@
+/// Lightweight heap accounting via a counting global allocator.
+///
+/// The allocator is only installed with the `heap-stats` feature. Without it, [`measure`] does not
+/// record anything and solutions use the system allocator as usual.
+use std::collections::HashMap;
+use tinyjson::JsonValue;
+
+/// Heap usage of a single run of a solution step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Highest number of bytes allocated at the same time, on top of what was allocated before the run.
+    pub peak_bytes: u64,
+    /// Sum of the sizes of all allocations.
+    pub total_bytes: u64,
+    /// Number of allocations.
+    pub allocations: u64,
+}
+
+impl HeapStats {
+    /// Formats the statistics in the form `[heap: peak 1.2 KiB, total 3.4 KiB, 12 allocs]`.
+    pub fn summary(&self) -> String {
+        format!(
+            "[heap: peak {}, total {}, {} allocs]",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024_f64;
+    let mut unit = 0;
+
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Run `f`, recording its heap usage if the counting allocator is installed.
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<HeapStats>) {
+    let baseline = counting::reset();
+    let result = f();
+    (result, Some(counting::read(baseline)))
+}
+
+/// Run `f`, recording its heap usage if the counting allocator is installed.
+#[cfg(not(all(feature = "heap-stats", not(feature = "dhat-heap"))))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<HeapStats>) {
+    (f(), None)
+}
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+mod counting {
+    use super::HeapStats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static CURRENT: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+    static TOTAL: AtomicU64 = AtomicU64::new(0);
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+    /// Wraps the system allocator, counting allocations and tracking the peak of allocated bytes.
+    pub struct CountingAlloc;
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size() as u64);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size() as u64);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+                record_alloc(new_size as u64);
+            }
+            new_ptr
+        }
+    }
+
+    fn record_alloc(size: u64) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        TOTAL.fetch_add(size, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Start a new measurement, returning the number of bytes allocated before it.
+    pub fn reset() -> u64 {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        TOTAL.store(0, Ordering::Relaxed);
+        ALLOCATIONS.store(0, Ordering::Relaxed);
+        current
+    }
+
+    pub fn read(baseline: u64) -> HeapStats {
+        HeapStats {
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(baseline),
+            total_bytes: TOTAL.load(Ordering::Relaxed),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+pub use counting::CountingAlloc;
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HeapStats> for JsonValue {
+    fn from(value: &HeapStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("peak_bytes".into(), JsonValue::Number(value.peak_bytes as f64));
+            map.insert("total_bytes".into(), JsonValue::Number(value.total_bytes as f64));
+            map.insert("allocations".into(), JsonValue::Number(value.allocations as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected heap.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(HeapStats {
+            peak_bytes: number("peak_bytes")? as u64,
+            total_bytes: number("total_bytes")? as u64,
+            allocations: number("allocations")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure, HeapStats};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn formats_summary() {
+        let stats = HeapStats {
+            peak_bytes: 2048,
+            total_bytes: 4096,
+            allocations: 3,
+        };
+        assert_eq!(
+            stats.summary(),
+            "[heap: peak 2.0 KiB, total 4.0 KiB, 3 allocs]"
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = HeapStats {
+            peak_bytes: 10,
+            total_bytes: 20,
+            allocations: 2,
+        };
+        let json = JsonValue::from(&stats);
+        assert_eq!(HeapStats::try_from(&json).unwrap(), stats);
+    }
+
+    #[test]
+    #[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+    fn measures_allocations() {
+        let (_, stats) = measure(|| {
+            let v: Vec<u64> = Vec::with_capacity(1000);
+            drop(v);
+            vec![0_u8; 100]
+        });
+        let stats = stats.unwrap();
+        assert!(stats.peak_bytes >= 8000);
+        assert!(stats.total_bytes >= 8100);
+        assert!(stats.allocations >= 2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "heap-stats"))]
+    fn measures_nothing_without_feature() {
+        assert_eq!(measure(|| 1), (1, None));
+    }
+}
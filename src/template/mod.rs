@@ -1,68 +1,216 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod compare;
+pub mod examples;
+pub mod ocr;
+pub mod registry;
+pub mod run_multi;
 pub mod runner;
+pub mod submissions;
 
 pub use day::*;
+pub use rng::*;
+pub use year::*;
 
+mod cpu_time;
+mod dashboard;
 mod day;
+mod heap;
 mod readme_benchmarks;
-mod run_multi;
+mod report;
+mod rng;
+mod scaling;
+mod stats;
+mod stress;
+mod templates;
 mod timings;
+mod watch;
+mod year;
+
+// NOTE: the allocators are defined here rather than in `solution!`, as the main binary links all solutions.
+// Every binary built with one of the features picks them up. DHAT takes precedence over heap stats.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: heap::CountingAlloc = heap::CountingAlloc;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Directory of the data files of the selected year, i.e. `data/<year>` if the year has its own directory, `data` otherwise.
+#[must_use]
+pub fn data_dir() -> PathBuf {
+    year_data_dir(Year::scoped())
+}
+
+fn year_data_dir(year: Option<Year>) -> PathBuf {
+    match year {
+        Some(year) => Path::new("data").join(year.to_string()),
+        None => PathBuf::from("data"),
+    }
+}
+
+/// Name of the solution binary of a day in the selected year, e.g. `01` or `2022-01`.
+#[must_use]
+pub fn bin_name(day: Day) -> String {
+    match Year::scoped() {
+        Some(year) => format!("{year}-{day}"),
+        None => day.to_string(),
+    }
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_year_file(folder, None, day)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_year_file_part(folder, None, day, part)
+}
+
+/// Like [`read_file`], but reads from the directory of `year`. Falls back to the selected year if `year` is `None`.
+#[must_use]
+pub fn read_year_file(folder: &str, year: Option<Year>, day: Day) -> String {
+    read_data_file(folder, year, &format!("{day}.txt"))
+}
+
+/// Like [`read_file_part`], but reads from the directory of `year`. Falls back to the selected year if `year` is `None`.
+#[must_use]
+pub fn read_year_file_part(folder: &str, year: Option<Year>, day: Day, part: u8) -> String {
+    read_data_file(folder, year, &format!("{day}-{part}.txt"))
+}
+
+fn read_data_file(folder: &str, year: Option<Year>, file: &str) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let dir = year.map_or_else(data_dir, |year| year_data_dir(Some(year)));
+    let filepath = cwd.join(dir).join(folder).join(file);
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
+/// Solutions of years with their own directory (e.g. `src/bin/2022-01.rs`) also get their year as `YEAR`, which
+/// selects the year when the binary runs.
+///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// A `parse = <function>` parameter can be passed to parse the input once and share the result
+/// between both parts, e.g. `solution!(5, parse = parse_input)`. The parser is timed separately
+/// from the parts, which then receive a reference to the parsed input.
+///
+/// A `variants = { <part> => [<function>, ...], ... }` parameter runs several implementations of a part, e.g.
+/// `solution!(5, variants = { 1 => [part_one, part_one_naive], 2 => [part_two] })`. Variants must agree on their
+/// answer and are timed side by side. The fastest variant is reported and stored as the timing of the part.
+///
+/// A trailing `generator = <function>` parameter registers a generator of random inputs, `fn(usize, u64) -> String`,
+/// which returns a valid input of the given size from a seed, e.g.
+/// `solution!(5, parse = parse_input, generator = generate_input)`. `cargo stress <day>` checks the solution against
+/// generated inputs, and `cargo time <day> --scaling` benchmarks it on generated inputs of growing size.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, generator = $gen:path)?) => {
+        $crate::solution!(@impl [$($gen)?] $day, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1 $(, generator = $gen:path)?) => {
+        $crate::solution!(@impl [$($gen)?] $day, [part_one, 1]);
+    };
+    ($day:expr, 2 $(, generator = $gen:path)?) => {
+        $crate::solution!(@impl [$($gen)?] $day, [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr $(, generator = $gen:path)?) => {
+        $crate::solution!(@impl_parsed [$($gen)?] $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1 $(, generator = $gen:path)?) => {
+        $crate::solution!(@impl_parsed [$($gen)?] $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2 $(, generator = $gen:path)?) => {
+        $crate::solution!(@impl_parsed [$($gen)?] $day, $parse, [part_two, 2]);
+    };
+    (
+        $day:expr, variants = { $( $part:literal => [$( $func:path ),+ $(,)?] ),+ $(,)? }
+        $(, generator = $gen:path)?
+    ) => {
+        $crate::solution!(@common [$($gen)?] $day, |input| {
+            use $crate::template::runner::*;
+            $(
+                let variants: &[Variant<_, _>] = &[$( (stringify!($func), &|input| $func(input)) ),+];
+                run_variants(variants, input, DAY, $part);
+            )+
+        });
+    };
+    (
+        $day:expr, parse = $parse:expr, variants = { $( $part:literal => [$( $func:path ),+ $(,)?] ),+ $(,)? }
+        $(, generator = $gen:path)?
+    ) => {
+        $crate::solution!(@common [$($gen)?] $day, |input| {
+            use $crate::template::runner::*;
+            let parsed = run_parse($parse, input, DAY);
+            $(
+                let variants: &[Variant<_, _>] = &[$( (stringify!($func), &|input| $func(input)) ),+];
+                run_variants(variants, &parsed, DAY, $part);
+            )+
+        });
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+
+    (@impl [$($gen:path)?] $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common [$($gen)?] $day, |input| {
+            use $crate::template::runner::*;
+            $( run_part($func, input, DAY, $part); )*
+        });
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+
+    (@impl_parsed [$($gen:path)?] $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common [$($gen)?] $day, |input| {
+            use $crate::template::runner::*;
+            let parsed = run_parse($parse, input, DAY);
+            $( run_part($func, &parsed, DAY, $part); )*
+        });
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@generator) => { None };
+    (@generator $gen:path) => { Some($gen) };
+
+    (@common [$($gen:path)?] $day:expr, $run:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The year of this solution if it has its own directory, i.e. for binaries like `2022-01`.
+        #[allow(dead_code)]
+        const YEAR: Option<$crate::template::Year> =
+            $crate::template::Year::from_bin_name(env!("CARGO_BIN_NAME"));
+
+        /// Entry point of this solution, picked up by the main binary's solution registry.
+        pub(crate) const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                run: $run,
+                generator: $crate::solution!(@generator $($gen)?),
+            };
 
         fn main() {
-            use $crate::template::runner::*;
+            if let Some(year) = YEAR {
+                year.set_current();
+            }
             let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            (SOLUTION.run)(&input);
         }
     };
 }
@@ -0,0 +1,232 @@
+//! Recognises the block letters that some puzzles draw as their answer.
+//!
+//! Supports the 4x6 font (e.g. 2016 day 8, 2022 day 10) and the 6x10 font (2018 day 10). Lit pixels are `#` or `█`,
+//! everything else counts as dark. Letters are separated by at least one dark column.
+
+/// Letters of the 4x6 font. `Y` is the only letter that is 5 columns wide.
+const SMALL_FONT: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters of the 6x10 font.
+const LARGE_FONT: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+type Glyph = Vec<Vec<bool>>;
+
+/// Decode block letters to text. Returns `None` unless every letter is recognised.
+pub fn decode(art: &str) -> Option<String> {
+    let glyph = parse(art);
+
+    let font = match glyph.len() {
+        6 => SMALL_FONT,
+        10 => LARGE_FONT,
+        _ => return None,
+    };
+
+    let width = glyph.iter().map(Vec::len).max()?;
+    let is_lit_column = |x: usize| glyph.iter().any(|row| row.get(x) == Some(&true));
+
+    let mut text = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if !is_lit_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && is_lit_column(x) {
+            x += 1;
+        }
+
+        let letter: Glyph = glyph
+            .iter()
+            .map(|row| (start..x).map(|i| row.get(i) == Some(&true)).collect())
+            .collect();
+
+        let (c, _) = font
+            .iter()
+            .find(|(_, pattern)| trim_columns(parse(pattern)) == letter)?;
+
+        text.push(*c);
+    }
+
+    (!text.is_empty()).then_some(text)
+}
+
+/// Parse pixels, dropping blank lines before and after the letters.
+fn parse(art: &str) -> Glyph {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+
+    let is_blank = |row: &Vec<bool>| !row.contains(&true);
+    let start = rows.iter().position(|row| !is_blank(row));
+    let end = rows.iter().rposition(|row| !is_blank(row));
+
+    match start.zip(end) {
+        Some((start, end)) => rows[start..=end].to_vec(),
+        None => vec![],
+    }
+}
+
+/// Drop the dark columns on both sides of a letter, as they are dropped when splitting art into letters.
+fn trim_columns(glyph: Glyph) -> Glyph {
+    let width = glyph.iter().map(Vec::len).max().unwrap_or_default();
+    let is_lit_column = |x: usize| glyph.iter().any(|row| row.get(x) == Some(&true));
+
+    let start = (0..width).find(|&x| is_lit_column(x)).unwrap_or_default();
+    let end = (0..width).rfind(|&x| is_lit_column(x)).map_or(0, |x| x + 1);
+
+    glyph
+        .iter()
+        .map(|row| (start..end).map(|x| row.get(x) == Some(&true)).collect())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::decode;
+
+    #[test]
+    fn decodes_small_letters() {
+        let art = "\
+###..#....###...##....##..##..#....#..#.
+#..#.#....#..#.#..#....#.#..#.#....#..#.
+#..#.#....###..#.......#.#....#....#..#.
+###..#....#..#.#.......#.#....#....#..#.
+#....#....#..#.#..#.#..#.#..#.#....#..#.
+#....####.###...##...##...##..####..##..";
+        assert_eq!(decode(art), Some("PLBCJCLU".into()));
+    }
+
+    #[test]
+    fn decodes_wide_letters() {
+        let art = "\
+#..#.####.#...#
+#..#.#....#...#
+####.###...#.#.
+#..#.#......#..
+#..#.#......#..
+#..#.####...#..";
+        assert_eq!(decode(art), Some("HEY".into()));
+    }
+
+    #[test]
+    fn decodes_blocks_and_spaces() {
+        let art = "\n\
+█  █ ████\n\
+█  █ █   \n\
+████ ███ \n\
+█  █ █   \n\
+█  █ █   \n\
+█  █ ████\n";
+        assert_eq!(decode(art), Some("HE".into()));
+    }
+
+    #[test]
+    fn decodes_large_letters() {
+        let art = "\
+#....#..######
+#....#..#.....
+#....#..#.....
+#....#..#.....
+######..#####.
+#....#..#.....
+#....#..#.....
+#....#..#.....
+#....#..#.....
+#....#..######";
+        assert_eq!(decode(art), Some("HE".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_letters() {
+        let art = "#...\n.#..\n..#.\n...#\n..#.\n.#..";
+        assert_eq!(decode(art), None);
+        assert_eq!(decode("42"), None);
+        assert_eq!(decode("#.#\n.#.\n#.#"), None);
+    }
+}
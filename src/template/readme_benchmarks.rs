@@ -3,10 +3,18 @@
 use std::{fs, io};
 
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Years with their own directory get a table of their own, e.g. `<!--- benchmarking table 2022 --->`.
+fn marker(year: Option<Year>) -> String {
+    match year {
+        Some(year) => format!("<!--- benchmarking table {year} --->"),
+        None => MARKER.into(),
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -26,12 +34,15 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Option<Year>, day: Day) -> String {
+    match year {
+        Some(year) => format!("./src/bin/{year}-{day}.rs"),
+        None => format!("./src/bin/{day}.rs"),
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,47 +63,71 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    year: Option<Year>,
+    timings: Timings,
+    total_millis: f64,
+) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Benchmarks"),
+        None => format!("{prefix} Benchmarks"),
+    };
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker(year),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(year, timing.day);
+        let missing = if timing.timed_out { "timed out" } else { "-" };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.parse.unwrap_or_else(|| "-".into()),
+            timing.part_1.unwrap_or_else(|| missing.into()),
+            timing.part_2.unwrap_or_else(|| missing.into())
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker(year));
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Option<Year>,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let marker = marker(year);
+
+    // the tables of further years are appended to the readme on their first update.
+    if year.is_some() && !s.contains(&marker) {
+        s.push_str(&format!("\n{marker}{marker}\n"));
+    }
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the benchmark table of the selected year in the readme with the most recent run of every day.
+pub fn update(timings: &Timings) -> Result<(), Error> {
+    let timings = timings.latest();
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, Year::scoped(), timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,28 +135,29 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{day, template::timings::Timing, template::timings::Timings, template::Year};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
+                    parse: Some("5ms".into()),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -131,28 +167,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +196,18 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `5ms` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +216,31 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_timed_out_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].timed_out = true;
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, None, timings, 190.0).unwrap();
+        assert_eq!(
+            s.contains("| [Day 4](./src/bin/04.rs) | `5ms` | `40ms` | `timed out` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn appends_tables_of_further_years() {
+        let year = Year::new(2022);
+        let mut s = format!("foo\n{}{}\n", MARKER, MARKER);
+        update_content(&mut s, year, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year, get_mock_timings(), 190.0).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("<!--- benchmarking table 2022 --->").count(), 2);
+        assert_eq!(s.matches("## 2022 Benchmarks").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2022-01.rs) | `-` | `10ms` | `20ms` |"));
+    }
 }
@@ -0,0 +1,25 @@
+/// Registry of solutions that are linked into the main binary.
+///
+/// Every `solution!` invocation defines a `SOLUTION` constant. With the `linked-solutions` feature,
+/// the build script includes all solution binaries as modules of the main binary and collects these
+/// constants, so that `all` and `time` can run every day in a single process instead of spawning
+/// `cargo` per day.
+use crate::template::{Day, Year};
+
+/// Entry point of a single day, as registered by the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    /// Year of solutions that have their own directory, see [`Year`].
+    pub year: Option<Year>,
+    pub day: Day,
+    /// Runs the parser (if any) and all parts of the solution against the given input.
+    pub run: fn(&str),
+    /// Generates a random input of the given size from a seed, see `cargo stress` and `cargo time <day> --scaling`.
+    pub generator: Option<fn(usize, u64) -> String>,
+}
+
+/// Look up the solution for a day of the selected year.
+pub fn find(solutions: &[Solution], day: Day) -> Option<&Solution> {
+    let year = Year::scoped();
+    solutions.iter().find(|s| s.day == day && s.year == year)
+}
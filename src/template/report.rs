@@ -0,0 +1,329 @@
+/// Machine-readable results that solution binaries hand to the process that invoked them.
+///
+/// When the `AOC_REPORT_FILE` environment variable is set, the runner appends one JSON object per
+/// line to that file for the parse step and every part. The human-readable output on stdout is not
+/// affected, so solutions are free to print whatever they like. Solutions that run in-process
+/// hand their reports over via [`collect`] instead.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    sync::Mutex,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{heap::HeapStats, stats::Stats, Day};
+
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// The step of a solution that a [`Report`] describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => f.pad("Parse"),
+            Step::Part(part) => f.pad(&format!("Part {part}")),
+        }
+    }
+}
+
+/// The outcome of running a single step of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub day: Day,
+    pub step: Step,
+    /// The answer of a part. `None` if the part did not produce an answer, or for the parse step.
+    pub answer: Option<String>,
+    /// Why a part failed to produce an answer, if it returned an error.
+    pub error: Option<String>,
+    /// Wall-clock time.
+    pub stats: Stats,
+    /// CPU time of the thread running the step. Not available on all platforms.
+    pub cpu_stats: Option<Stats>,
+    /// Heap usage of the first run, only recorded with the `heap-stats` feature.
+    pub heap: Option<HeapStats>,
+}
+
+impl Report {
+    /// Whether this step failed to produce an answer.
+    pub fn is_failure(&self) -> bool {
+        matches!(self.step, Step::Part(_)) && self.answer.is_none()
+    }
+
+    /// Mean duration, formatted the same way as in the human-readable output.
+    pub fn time(&self) -> String {
+        format!("{:.1?}", self.stats.mean_duration())
+    }
+}
+
+static COLLECTOR: Mutex<Option<Vec<Report>>> = Mutex::new(None);
+
+/// Run `f`, collecting the reports emitted while it runs instead of writing them to the report file.
+/// Used when solutions run in-process.
+pub fn collect(f: impl FnOnce()) -> Vec<Report> {
+    *COLLECTOR.lock().unwrap() = Some(vec![]);
+    f();
+    COLLECTOR.lock().unwrap().take().unwrap_or_default()
+}
+
+/// Like [`collect`], but catches panics of `f`, e.g. of a solution that runs on a generated input.
+/// Returns the reports emitted before the panic, and the panic message.
+pub fn collect_catching(f: impl FnOnce()) -> (Vec<Report>, Option<String>) {
+    let mut result = Ok(());
+
+    // the default hook would print a backtrace for every panic.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let reports = collect(|| result = panic::catch_unwind(AssertUnwindSafe(f)));
+
+    panic::set_hook(hook);
+
+    let message = result.err().map(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default()
+    });
+
+    (reports, message)
+}
+
+/// Append a report to the active collector, or to the file configured via [`REPORT_FILE_ENV`], if any.
+pub fn emit(report: &Report) {
+    if let Some(reports) = COLLECTOR.lock().unwrap().as_mut() {
+        reports.push(report.clone());
+        return;
+    }
+
+    let Ok(path) = env::var(REPORT_FILE_ENV) else {
+        return;
+    };
+
+    let line = JsonValue::from(report).stringify().map_err(io::Error::other);
+
+    let result = line.and_then(|line| {
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{line}")
+    });
+
+    if let Err(e) = result {
+        eprintln!("Failed to write report to \"{path}\": {e}");
+    }
+}
+
+/// Path of a fresh report file for one invocation of a solution binary.
+pub fn report_file_path(day: Day) -> PathBuf {
+    env::temp_dir().join(format!("aoc-report-{}-{day}.jsonl", process::id()))
+}
+
+/// Read all reports from a report file, removing it afterwards. A missing file yields no reports.
+pub fn read_reports(path: &Path) -> Result<Vec<Report>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let _ = fs::remove_file(path);
+    parse_reports(&content)
+}
+
+/// Parse reports from JSON lines. Blank lines are ignored.
+pub fn parse_reports(content: &str) -> Result<Vec<Report>, String> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err("report is not valid JSON."))?;
+            Report::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Report> for JsonValue {
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "step".into(),
+            JsonValue::String(match value.step {
+                Step::Parse => "parse".into(),
+                Step::Part(part) => part.to_string(),
+            }),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("failed".into(), JsonValue::Boolean(value.is_failure()));
+
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+
+        map.insert("time".into(), JsonValue::String(value.time()));
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+
+        if let Some(cpu_stats) = &value.cpu_stats {
+            map.insert("cpu_stats".into(), JsonValue::from(cpu_stats));
+        }
+
+        if let Some(heap) = &value.heap {
+            map.insert("heap".into(), JsonValue::from(heap));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Report {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let step = match json.get("step").and_then(|v| v.get::<String>()) {
+            Some(s) if s == "parse" => Step::Parse,
+            Some(s) => Step::Part(s.parse().or(Err("Expected report.step to be a part."))?),
+            None => return Err("Expected report.step to be a string.".into()),
+        };
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let stats = Stats::try_from(json.get("stats").ok_or("Expected report.stats.")?)?;
+
+        let error = json.get("error").and_then(|v| v.get::<String>()).cloned();
+
+        let cpu_stats = json.get("cpu_stats").map(Stats::try_from).transpose()?;
+        let heap = json.get("heap").map(HeapStats::try_from).transpose()?;
+
+        Ok(Report {
+            day,
+            step,
+            answer: answer.cloned(),
+            error,
+            stats,
+            cpu_stats,
+            heap,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_reports, Report, Step};
+    use crate::{
+        day,
+        template::{heap::HeapStats, stats::Stats},
+    };
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn get_mock_report(step: Step, answer: Option<&str>) -> Report {
+        Report {
+            day: day!(1),
+            step,
+            answer: answer.map(String::from),
+            error: None,
+            stats: Stats::from_samples(&[Duration::from_micros(2), Duration::from_micros(4)]),
+            cpu_stats: Some(Stats::from_samples(&[Duration::from_micros(1)])),
+            heap: None,
+        }
+    }
+
+    fn roundtrip(reports: &[Report]) -> Vec<Report> {
+        let content = reports
+            .iter()
+            .map(|r| JsonValue::from(r).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        parse_reports(&content).unwrap()
+    }
+
+    #[test]
+    fn roundtrips_reports() {
+        let reports = [
+            get_mock_report(Step::Parse, None),
+            get_mock_report(Step::Part(1), Some("42")),
+            get_mock_report(Step::Part(2), None),
+        ];
+        assert_eq!(roundtrip(&reports), reports);
+    }
+
+    #[test]
+    fn roundtrips_answers_with_patterns() {
+        let reports = [
+            get_mock_report(Step::Part(1), Some("@ @ @ ( ) ms (2s @ 5 samples)")),
+            get_mock_report(Step::Part(2), Some("#..#\n#..#\n####")),
+        ];
+        assert_eq!(roundtrip(&reports), reports);
+    }
+
+    #[test]
+    fn roundtrips_errors() {
+        let mut report = get_mock_report(Step::Part(1), None);
+        report.error = Some("invalid digit found in string".into());
+        assert_eq!(roundtrip(&[report.clone()]), [report]);
+    }
+
+    #[test]
+    fn roundtrips_heap_stats() {
+        let mut report = get_mock_report(Step::Part(1), Some("42"));
+        report.heap = Some(HeapStats {
+            peak_bytes: 1024,
+            total_bytes: 4096,
+            allocations: 8,
+        });
+        assert_eq!(roundtrip(&[report.clone()]), [report]);
+    }
+
+    #[test]
+    fn marks_failures() {
+        assert_eq!(get_mock_report(Step::Parse, None).is_failure(), false);
+        assert_eq!(get_mock_report(Step::Part(1), Some("1")).is_failure(), false);
+        assert_eq!(get_mock_report(Step::Part(1), None).is_failure(), true);
+    }
+
+    #[test]
+    fn ignores_blank_lines() {
+        assert_eq!(parse_reports("\n\n").unwrap().len(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_reports() {
+        parse_reports(r#"{ "day": "01", "step": "3" }"#).unwrap();
+    }
+}
@@ -0,0 +1,83 @@
+use std::ops::Range;
+
+/// A small, seeded random number generator (SplitMix64) for input generators.
+///
+/// The same seed always produces the same numbers, which makes generated inputs reproducible. Not suitable for
+/// anything that needs real randomness.
+///
+/// ```
+/// # use advent_of_code::template::Rng;
+/// let mut rng = Rng::new(42);
+/// let roll = rng.range(1..7);
+/// assert!((1..7).contains(&roll));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A random number in `range`. Panics if the range is empty.
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(
+            !range.is_empty(),
+            "cannot pick a number from an empty range"
+        );
+        let len = range.end.abs_diff(range.start);
+        range.start.wrapping_add_unsigned(self.next_u64() % len)
+    }
+
+    /// `true` with the given probability, e.g. `0.25`.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        #[allow(clippy::cast_precision_loss)]
+        let sample = (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64;
+        sample < probability
+    }
+
+    /// A random item of `items`. Panics if `items` is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        let len = items.len() as i64;
+        &items[self.range(0..len) as usize]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn is_reproducible() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..1000 {
+            assert!((-3..4).contains(&rng.range(-3..4)));
+            assert!(['a', 'b'].contains(rng.pick(&['a', 'b'])));
+        }
+
+        assert!(!rng.chance(0_f64));
+        assert!(rng.chance(1_f64));
+    }
+}
@@ -1,56 +1,388 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::{self, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::{self, Verdict};
+use crate::template::registry::{self, Solution};
+use crate::template::report::{Report, Step};
+use crate::template::{bin_name, runner, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Options shared by all solutions of a [`run_multi`] invocation.
+pub struct RunOptions<'a> {
+    pub is_release: bool,
+    pub is_timed: bool,
+    /// Number of solutions that run at the same time.
+    pub jobs: usize,
+    pub timeouts: Timeouts,
+    /// Solutions linked into the main binary.
+    pub solutions: &'a [Solution],
+}
+
+impl RunOptions<'_> {
+    /// Registered solutions can only stand in for a solution binary if the main binary was built with the same profile.
+    pub fn use_registry(&self) -> bool {
+        self.is_release != cfg!(debug_assertions)
+    }
+}
+
+/// Limits after which a solution is killed. A solution that runs into a timeout is reported as "timed out".
+///
+/// Timeouts are disabled by default. Enabling one runs every day in its own process, so that it can be killed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Maximum duration of a whole day, including the compilation of its binary.
+    pub day: Option<Duration>,
+    /// Maximum duration of a single step (i.e. part 1 or part 2) of a day, counted from the report of the
+    /// previous step.
+    pub part: Option<Duration>,
+}
+
+impl Timeouts {
+    /// Build timeouts from seconds, where a missing value or `0` disables the respective timeout.
+    pub fn from_secs(day: Option<u64>, part: Option<u64>) -> Self {
+        let to_timeout = |secs: Option<u64>| secs.filter(|&secs| secs > 0).map(Duration::from_secs);
+
+        Timeouts {
+            day: to_timeout(day),
+            part: to_timeout(part),
+        }
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.day.is_none() && self.part.is_none()
+    }
+}
+
+/// Outcome of running the solution of a single day.
+pub struct DayRun {
+    pub reports: Vec<Report>,
+    /// Whether the solution was killed after running into a timeout.
+    pub timed_out: bool,
+    /// Why the solution failed as a whole, e.g. because it panicked or exited with a non-zero status.
+    pub error: Option<String>,
+    /// Output of the solution if it was captured instead of forwarded to the terminal.
+    pub output: Option<BufferedOutput>,
+}
 
-    let mut need_space = false;
+/// Output of a solution that was captured instead of forwarded to the terminal.
+pub struct BufferedOutput {
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// Result of checking the answer of a single part against the known answers.
+pub struct Check {
+    pub day: Day,
+    pub part: u8,
+    pub verdict: Verdict,
+}
+
+/// A part that returned an error instead of an answer, or a solution that failed as a whole.
+pub struct Failure {
+    pub day: Day,
+    /// `None` if the whole solution failed, e.g. because it panicked.
+    pub part: Option<u8>,
+    pub reason: String,
+}
+
+/// Outcome of a [`run_multi`] invocation.
+pub struct Summary {
+    pub timings: Timings,
+    pub checks: Vec<Check>,
+    pub failures: Vec<Failure>,
+}
+
+impl Summary {
+    /// Whether any part produced an answer that differs from the known answer.
+    pub fn has_wrong_answers(&self) -> bool {
+        self.checks.iter().any(|c| c.verdict == Verdict::Wrong)
+    }
+}
+
+/// Run the solutions for a set of days.
+///
+/// Registered solutions run in-process if the main binary was built with the requested profile
+/// and timeouts are disabled. With timeouts, they run as a child process of the main binary, so
+/// that they can be killed. All other solutions are invoked via `cargo run`.
+///
+/// With `jobs > 1`, up to `jobs` solutions run at the same time as child processes. Their output is
+/// buffered and printed in day order once available.
+///
+/// The answers of all parts are checked against the known answers in `data/answers.json`.
+pub fn run_multi(days_to_run: &HashSet<Day>, options: &RunOptions) -> Summary {
+    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut timed_out: Vec<Day> = vec![];
+    let mut checks: Vec<Check> = vec![];
+    let mut failures: Vec<Failure> = vec![];
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    if options.jobs > 1 {
+        if options.is_timed {
+            eprintln!(
+                "Warning: running {} solutions in parallel, benchmarks may interfere with each other.",
+                options.jobs
+            );
+        }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        run_parallel(&days, options, |day, run| {
+            let run = run.unwrap();
+            print_day_header(day, day != days[0]);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            if let Some(output) = run.as_ref().and_then(|r| r.output.as_ref()) {
+                let _ = io::stdout().write_all(&output.stdout);
+                let _ = io::stderr().write_all(&output.stderr);
             }
+
+            record_run(
+                day,
+                run,
+                &mut timings,
+                &mut timed_out,
+                &mut checks,
+                &mut failures,
+            );
         });
+    } else {
+        runner::set_timed(options.is_timed);
+
+        for (i, &day) in days.iter().enumerate() {
+            print_day_header(day, i > 0);
+            let run = run_day(day, options, false).unwrap();
+            record_run(
+                day,
+                run,
+                &mut timings,
+                &mut timed_out,
+                &mut checks,
+                &mut failures,
+            );
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    if !timed_out.is_empty() {
+        let days = timed_out
+            .iter()
+            .map(|day| format!("Day {day}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("\n{ANSI_BOLD}Timed out:{ANSI_RESET} {days}");
+    }
+
+    if !failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET}");
+        for failure in &failures {
+            match failure.part {
+                Some(part) => println!("Day {} Part {part}: {}", failure.day, failure.reason),
+                None => println!("Day {}: {}", failure.day, failure.reason),
+            }
+        }
+    }
+
+    print_checks(&checks);
+
+    let timings = Timings { data: timings };
+
+    if options.is_timed {
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+    }
+
+    Summary {
+        timings,
+        checks,
+        failures,
+    }
+}
+
+/// Print a summary of the checked answers. Nothing is printed if no answers are known.
+fn print_checks(checks: &[Check]) {
+    let count = |verdict: Verdict| checks.iter().filter(|c| c.verdict == verdict).count();
+
+    if count(Verdict::Unknown) == checks.len() {
+        return;
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Answers:{ANSI_RESET} {} correct, {} wrong, {} unknown",
+        count(Verdict::Correct),
+        count(Verdict::Wrong),
+        count(Verdict::Unknown)
+    );
+
+    let wrong = checks
+        .iter()
+        .filter(|c| c.verdict == Verdict::Wrong)
+        .map(|c| format!("Day {} Part {}", c.day, c.part))
+        .collect::<Vec<_>>();
+
+    if !wrong.is_empty() {
+        println!("{ANSI_BOLD}Wrong:{ANSI_RESET} {}", wrong.join(", "));
+    }
+}
+
+fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+fn record_run(
+    day: Day,
+    run: Option<DayRun>,
+    timings: &mut Vec<Timing>,
+    timed_out: &mut Vec<Day>,
+    checks: &mut Vec<Check>,
+    failures: &mut Vec<Failure>,
+) {
+    let Some(run) = run else {
+        println!("Not solved.");
+        return;
+    };
+
+    if run.timed_out {
+        println!("Timed out.");
+        timed_out.push(day);
+    }
+
+    checks.extend(check_answers(&run.reports, day, answers::known()));
+
+    failures.extend(run.reports.iter().filter_map(|report| match report.step {
+        Step::Part(part) => report.error.clone().map(|reason| Failure {
+            day,
+            part: Some(part),
+            reason,
+        }),
+        Step::Parse => None,
+    }));
+
+    if let Some(reason) = run.error {
+        failures.push(Failure {
+            day,
+            part: None,
+            reason,
+        });
+    }
+
+    let mut timing = child_commands::timing_from_reports(&run.reports, day);
+    timing.timed_out = run.timed_out;
+    timings.push(timing);
+}
+
+/// Check the answers of both parts of a day. Parts without a report are only checked if their answer is known,
+/// as solutions may implement only one part.
+fn check_answers(reports: &[Report], day: Day, answers: &answers::Answers) -> Vec<Check> {
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let report = reports
+                .iter()
+                .find(|r| r.day == day && r.step == Step::Part(part));
+
+            if report.is_none() && answers.get(day, part).is_none() {
+                return None;
+            }
+
+            let answer = report.and_then(|r| r.answer.as_deref());
+            Some(Check {
+                day,
+                part,
+                verdict: answers.verify(day, part, answer),
+            })
+        })
+        .collect()
+}
+
+/// Run the solution for a single day, picking the cheapest way that satisfies the options.
+/// Returns `None` if the day has not been scaffolded yet.
+fn run_day(day: Day, options: &RunOptions, buffered: bool) -> Result<Option<DayRun>, Error> {
+    match registry::find(options.solutions, day) {
+        Some(solution) if options.use_registry() && !buffered && options.timeouts.is_disabled() => {
+            let (reports, error) = in_process::run_solution(solution);
+            Ok(Some(DayRun {
+                reports,
+                timed_out: false,
+                error,
+                output: None,
+            }))
+        }
+        Some(_) if options.use_registry() => {
+            child_commands::run_solution(day, options, true, buffered).map(Some)
+        }
+        _ => {
+            // skip command invocation for days that have not been scaffolded yet.
+            if !Path::new(&get_path_for_bin(day)).exists() {
+                return Ok(None);
+            }
+            child_commands::run_solution(day, options, false, buffered).map(Some)
+        }
     }
 }
 
+/// Run solutions on a pool of `options.jobs` threads, handing each result to `on_done` in day order.
+fn run_parallel(
+    days: &[Day],
+    options: &RunOptions,
+    mut on_done: impl FnMut(Day, Result<Option<DayRun>, Error>),
+) {
+    let next_day = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next_day = &next_day;
+
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next_day.fetch_add(1, Ordering::Relaxed)) {
+                    if tx.send((day, run_day(day, options, true))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // NOTE: drop the original sender so the receiver finishes once all workers are done.
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut days_in_order = days.iter().peekable();
+
+        for (day, run) in rx {
+            pending.insert(day, run);
+
+            while let Some(run) = days_in_order.peek().and_then(|day| pending.remove(day)) {
+                on_done(*days_in_order.next().unwrap(), run);
+            }
+        }
+    });
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Report(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -61,134 +393,231 @@ impl From<std::io::Error> for Error {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    format!("./src/bin/{}.rs", bin_name(day))
+}
+
+/// Solutions linked into the main binary via the registry can run without spawning a child process.
+pub mod in_process {
+    use crate::template::read_file;
+    use crate::template::registry::Solution;
+    use crate::template::report::{self, Report};
+    use std::panic;
+
+    /// Run a registered solution against its input, returning the reports of its parse step and parts.
+    /// A panicking solution is reported with the parts it finished before panicking, and an error.
+    pub fn run_solution(solution: &Solution) -> (Vec<Report>, Option<String>) {
+        let mut result = Ok(());
+
+        let reports = report::collect(|| {
+            // NOTE: the default panic hook already prints the panic message.
+            result = panic::catch_unwind(|| {
+                let input = read_file("inputs", solution.day);
+                (solution.run)(&input);
+            });
+        });
+
+        (reports, result.err().map(|_| "panicked".to_string()))
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the results they report.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{BufferedOutput, DayRun, Error, RunOptions};
+    use crate::template::report::{self, Report, Step, REPORT_FILE_ENV};
+    use crate::template::{bin_name, Day};
     use std::{
-        io::{BufRead, BufReader},
+        env, fs,
+        io::Read,
         path::Path,
-        process::{Command, Stdio},
+        process::{Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Run the solution for a given day as a child process, killing it if it exceeds the configured timeouts.
+    ///
+    /// Registered solutions are run via the `run-day` command of the main binary, everything else via `cargo run`.
+    pub fn run_solution(
+        day: Day,
+        options: &RunOptions,
+        use_registry: bool,
+        buffered: bool,
+    ) -> Result<DayRun, Error> {
+        let mut cmd = if use_registry {
+            let mut cmd = Command::new(env::current_exe()?);
+            cmd.args(["run-day", &day.to_string()]);
+            if options.is_timed {
+                cmd.arg("--time");
+            }
+            cmd
+        } else {
+            let mut cmd = Command::new("cargo");
+            cmd.args(build_args(day, options.is_timed, options.is_release));
+            cmd
+        };
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        // results are reported via a separate file, so output can go straight to the terminal.
+        let report_path = report::report_file_path(day);
+        // NOTE: remove leftovers of an earlier run, so that only reports of this run are read.
+        let _ = fs::remove_file(&report_path);
+        let stdio = || {
+            if buffered {
+                Stdio::piped()
+            } else {
+                Stdio::inherit()
+            }
+        };
 
-        if is_release {
-            args.push("--release");
-        }
+        let mut child = cmd
+            .env(REPORT_FILE_ENV, &report_path)
+            .stdout(stdio())
+            .stderr(stdio())
+            .spawn()?;
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        let stdout = child.stdout.take().map(read_to_end);
+        let stderr = child.stderr.take().map(read_to_end);
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        let status = wait_with_timeouts(&mut child, &report_path, options)?;
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let output = match (stdout, stderr) {
+            (Some(stdout), Some(stderr)) => Some(BufferedOutput {
+                stdout: stdout.join().map_err(|_| Error::BrokenPipe)?,
+                stderr: stderr.join().map_err(|_| Error::BrokenPipe)?,
+            }),
+            _ => None,
+        };
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        Ok(DayRun {
+            reports: report::read_reports(&report_path).map_err(Error::Report)?,
+            timed_out: status.is_none(),
+            error: status
+                .filter(|status| !status.success())
+                .map(|status| format!("exited with {status}")),
+            output,
+        })
+    }
 
-        let mut output = vec![];
+    fn read_to_end(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut buf = vec![];
+            let _ = pipe.read_to_end(&mut buf);
+            buf
+        })
+    }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+    /// Wait for a child to exit. Returns its exit status, or `None` if it was killed after exceeding a timeout.
+    ///
+    /// The part timeout starts with the first report of the child, so that neither compiling its binary nor
+    /// starting it counts towards the first timed step, and restarts whenever the child reports a finished step.
+    fn wait_with_timeouts(
+        child: &mut Child,
+        report_path: &Path,
+        options: &RunOptions,
+    ) -> Result<Option<ExitStatus>, Error> {
+        let start = Instant::now();
+        let mut step_start = None;
+        let mut steps_done = 0;
+
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            if options.timeouts.part.is_some() {
+                let steps = fs::read_to_string(report_path).map_or(0, |s| s.lines().count());
+                if steps > steps_done {
+                    steps_done = steps;
+                    step_start = Some(Instant::now());
+                }
+            }
+
+            let is_day_expired = options.timeouts.day.is_some_and(|t| start.elapsed() > t);
+            let is_part_expired = options
+                .timeouts
+                .part
+                .zip(step_start)
+                .is_some_and(|(t, step_start)| step_start.elapsed() > t);
+
+            if is_day_expired || is_part_expired {
+                child.kill()?;
+                child.wait()?;
+                return Ok(None);
+            }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            thread::sleep(POLL_INTERVAL);
         }
+    }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    fn build_args(day: Day, is_timed: bool, is_release: bool) -> Vec<String> {
+        let mut args = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            bin_name(day),
+        ];
 
-        Ok(output)
+        if is_release {
+            args.push("--release".into());
+        }
+
+        if cfg!(feature = "heap-stats") {
+            // mirror heap accounting to child builds.
+            args.push("--features".into());
+            args.push("heap-stats".into());
+        }
+
+        if is_timed {
+            // mirror `--time` flag to child invocations.
+            args.push("--".into());
+            args.push("--time".into());
+        }
+
+        args
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Collect the reports of a solution run into a [`super::Timing`]. Parts that did not produce an answer are not timed.
+    pub fn timing_from_reports(reports: &[Report], day: Day) -> super::Timing {
+        let mut timings = super::Timing::new(day);
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+            .filter(|r| r.day == day && !r.is_failure())
+            .for_each(|r| {
+                let (time, stats, cpu_stats, heap) = match r.step {
+                    Step::Parse => (
+                        &mut timings.parse,
+                        &mut timings.parse_stats,
+                        &mut timings.parse_cpu_stats,
+                        &mut timings.parse_heap,
+                    ),
+                    Step::Part(1) => (
+                        &mut timings.part_1,
+                        &mut timings.part_1_stats,
+                        &mut timings.part_1_cpu_stats,
+                        &mut timings.part_1_heap,
+                    ),
+                    Step::Part(2) => (
+                        &mut timings.part_2,
+                        &mut timings.part_2_stats,
+                        &mut timings.part_2_cpu_stats,
+                        &mut timings.part_2_heap,
+                    ),
+                    Step::Part(_) => return,
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
+                *time = Some(r.time());
+                *stats = Some(r.stats.clone());
+                cpu_stats.clone_from(&r.cpu_stats);
+                *heap = r.heap;
+                timings.total_nanos += r.stats.mean;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,47 +634,95 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::timing_from_reports;
+        use crate::template::answers::{Answers, Verdict};
+        use crate::template::heap::HeapStats;
+        use crate::template::report::{Report, Step};
+        use crate::template::run_multi::check_answers;
+        use crate::template::stats::Stats;
+        use std::time::Duration;
 
         use crate::day;
 
+        fn report(step: Step, answer: Option<&str>, nanos: &[u64]) -> Report {
+            let samples: Vec<Duration> = nanos.iter().map(|&n| Duration::from_nanos(n)).collect();
+            Report {
+                day: day!(1),
+                step,
+                answer: answer.map(String::from),
+                error: None,
+                stats: Stats::from_samples(&samples),
+                cpu_stats: None,
+                heap: None,
+            }
+        }
+
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(Step::Part(1), Some("0"), &[74, 76]),
+                    report(Step::Part(2), Some("10"), &[74_130_000]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130075_f64);
+            assert_eq!(res.part_1.unwrap(), "75.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_1_stats.unwrap().samples, 2);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answers() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(Step::Part(1), Some("@ @ @ ( ) ms"), &[2_000_000_000]),
+                    report(Step::Part(2), Some("10s (1ms @ 5 samples)"), &[100_000_000]),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
+        }
+
+        #[test]
+        fn collects_parse_times() {
+            let res = timing_from_reports(
+                &[
+                    report(Step::Parse, None, &[1_000_000]),
+                    report(Step::Part(1), Some("0"), &[2_000_000]),
+                    report(Step::Part(2), Some("10"), &[3_000_000]),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 6000000_f64);
+            assert_eq!(res.parse.unwrap(), "1.0ms");
+            assert_eq!(res.part_1.unwrap(), "2.0ms");
+            assert_eq!(res.part_2.unwrap(), "3.0ms");
+        }
+
+        #[test]
+        fn collects_heap_stats() {
+            let heap = HeapStats {
+                peak_bytes: 2048,
+                total_bytes: 8192,
+                allocations: 4,
+            };
+            let mut part_1 = report(Step::Part(1), Some("0"), &[10]);
+            part_1.heap = Some(heap);
+            let res =
+                timing_from_reports(&[part_1, report(Step::Part(2), Some("10"), &[10])], day!(1));
+            assert_eq!(res.part_1_heap, Some(heap));
+            assert_eq!(res.part_2_heap, None);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_missing_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(Step::Part(1), None, &[10]),
+                    report(Step::Part(2), None, &[10]),
                 ],
                 day!(1),
             );
@@ -253,5 +730,30 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn checks_answers() {
+            let mut answers = Answers::default();
+            answers.insert(day!(1), 1, "0".into());
+            answers.insert(day!(1), 2, "11".into());
+
+            let checks = check_answers(
+                &[
+                    report(Step::Part(1), Some("0"), &[10]),
+                    report(Step::Part(2), Some("10"), &[10]),
+                ],
+                day!(1),
+                &answers,
+            );
+            assert_eq!(checks[0].verdict, Verdict::Correct);
+            assert_eq!(checks[1].verdict, Verdict::Wrong);
+
+            let checks = check_answers(&[], day!(1), &Answers::default());
+            assert_eq!(checks.len(), 0);
+
+            let checks = check_answers(&[], day!(1), &answers);
+            assert_eq!(checks.len(), 2);
+            assert_eq!(checks[0].verdict, Verdict::Wrong);
+        }
     }
 }
@@ -2,105 +2,363 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers;
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::cpu_time::CpuTimer;
+use crate::template::heap::{self, HeapStats};
+use crate::template::ocr;
+use crate::template::report::{self, Report, Step};
+use crate::template::stats::Stats;
+use crate::template::submissions::{SubmissionVerdict, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+static FORCE_TIMED: AtomicBool = AtomicBool::new(false);
+
+/// Bench solution parts even if the `--time` flag was not passed. Used when solutions run in-process.
+pub fn set_timed(is_timed: bool) {
+    FORCE_TIMED.store(is_timed, Ordering::Relaxed);
+}
+
+fn is_timed() -> bool {
+    FORCE_TIMED.load(Ordering::Relaxed) || env::args().any(|x| x == "--time")
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Run solution parts without printing their results, checking them against known answers or submitting them.
+/// Used when solutions run on inputs other than the puzzle input.
+pub fn set_quiet(is_quiet: bool) {
+    QUIET.store(is_quiet, Ordering::Relaxed);
+}
+
+fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Outcome of a solution part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an error, formatted with its chain of causes.
+    Failed(String),
+}
+
+/// Return types accepted for solution parts: `Option<T>`, `Result<T, E>` and thus `anyhow::Result<T>`.
+pub trait PartResult {
+    fn outcome(&self) -> Outcome;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Answer(answer.to_string()),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Answer(answer.to_string()),
+            // NOTE: the alternate form prints the causes of `anyhow::Error`, e.g. "failed to parse: invalid digit".
+            Err(e) => Outcome::Failed(format!("{e:#}")),
+        }
+    }
+}
+
+pub fn run_part<I: Clone, R: PartResult>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
+    let part_str = format!("Part {part}");
+
+    let (result, measurement) = run_timed(func, input, |result| {
+        print_result(&result.outcome(), &part_str, "");
+    });
+
+    finish_part(result.outcome(), measurement, day, part);
+}
+
+/// A named implementation of a solution part, e.g. `("part_one_naive", &part_one_naive)`.
+pub type Variant<'a, I, R> = (&'a str, &'a dyn Fn(I) -> R);
+
+/// Run every variant of a solution part and compare their timings. The part fails if the variants disagree.
+/// Otherwise, the fastest variant is reported, which stores its timing.
+pub fn run_variants<I: Clone, R: PartResult>(
+    variants: &[Variant<I, R>],
+    input: I,
+    day: Day,
+    part: u8,
+) {
+    let mut runs: Vec<(&str, Outcome, Measurement)> = variants
+        .iter()
+        .map(|(name, func)| {
+            let label = format!("Part {part} ({name})");
+
+            let (result, measurement) = run_timed(func, input.clone(), |result| {
+                print_result(&result.outcome(), &label, "");
+            });
+
+            let outcome = result.outcome();
+            print_result(&outcome, &label, &measurement.format());
+            (*name, outcome, measurement)
+        })
+        .collect();
+
+    let Some(fastest) = (0..runs.len()).min_by_key(|&i| runs[i].2.stats.mean_duration()) else {
+        return;
+    };
+
+    let outcome = agreed_outcome(
+        &runs
+            .iter()
+            .map(|(name, outcome, _)| (*name, outcome.clone()))
+            .collect::<Vec<_>>(),
+    );
+
+    let fastest_time = runs[fastest].2.stats.mean_duration();
+    let comparison = runs
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _, measurement))| {
+            let time = measurement.stats.mean_duration();
+            if i == fastest {
+                format!("{ANSI_BOLD}{name}{ANSI_RESET} {time:.1?} (fastest)")
+            } else {
+                let factor = time.as_secs_f64() / fastest_time.as_secs_f64().max(f64::EPSILON);
+                format!("{name} {time:.1?} ({factor:.1}x)")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
+
+    let (_, _, measurement) = runs.swap_remove(fastest);
+    finish_part(outcome, measurement, day, part);
+
+    if variants.len() > 1 && !is_quiet() {
+        println!("  {comparison}");
+    }
+}
+
+/// The outcome shared by all variants of a part, or a failure listing the outcome of each variant.
+fn agreed_outcome(outcomes: &[(&str, Outcome)]) -> Outcome {
+    match outcomes {
+        [] => Outcome::Unsolved,
+        [(_, first), rest @ ..] if rest.iter().all(|(_, outcome)| outcome == first) => {
+            first.clone()
+        }
+        _ => {
+            let results = outcomes
+                .iter()
+                .map(|(name, outcome)| match outcome {
+                    Outcome::Answer(answer) => format!("{name} = {answer}"),
+                    Outcome::Unsolved => format!("{name} = None"),
+                    Outcome::Failed(reason) => format!("{name} failed ({reason})"),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            Outcome::Failed(format!("variants disagree: {results}"))
+        }
+    }
+}
+
+/// Print the final result of a part, then report and possibly submit it.
+fn finish_part(outcome: Outcome, measurement: Measurement, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (answer, error) = match &outcome {
+        // answers drawn in block letters are checked and submitted as the text they spell.
+        Outcome::Answer(answer) => (Some(ocr::decode(answer).unwrap_or(answer.clone())), None),
+        Outcome::Unsolved => (None, None),
+        Outcome::Failed(reason) => (None, Some(reason.clone())),
+    };
+    if !is_quiet() {
+        let mark = answers::known().mark(day, part, answer.as_deref());
+
+        print_result(
+            &outcome,
+            &part_str,
+            &format!("{}{mark}", measurement.format()),
+        );
+    }
+
+    report::emit(&Report {
+        day,
+        step: Step::Part(part),
+        answer: answer.clone(),
+        error,
+        stats: measurement.stats,
+        cpu_stats: measurement.cpu_stats,
+        heap: measurement.heap,
+    });
+
+    if let Some(answer) = answer.filter(|_| !is_quiet()) {
+        submit_result(answer, day, part);
+    }
+}
+
+/// Run the input parser of a solution, timing it the same way as a solution part.
+pub fn run_parse<P>(func: impl Fn(&str) -> P, input: &str, day: Day) -> P {
+    let (parsed, measurement) = run_timed(func, input, |_| {
+        if !is_quiet() {
+            print!("Parse:");
+        }
+    });
+
+    if !is_quiet() {
+        print!("\r");
+        println!("Parse:{}", measurement.format());
+    }
+
+    report::emit(&Report {
+        day,
+        step: Step::Parse,
+        answer: None,
+        error: None,
+        stats: measurement.stats,
+        cpu_stats: measurement.cpu_stats,
+        heap: measurement.heap,
+    });
+
+    parsed
+}
+
+/// Everything measured while running a single step of a solution.
+struct Measurement {
+    /// Wall-clock time.
+    stats: Stats,
+    /// CPU time of the running thread. Not available on all platforms.
+    cpu_stats: Option<Stats>,
+    heap: Option<HeapStats>,
+}
+
+impl Measurement {
+    fn format(&self) -> String {
+        let duration = self.stats.mean_duration();
+
+        let cpu = self
+            .cpu_stats
+            .as_ref()
+            .map(|cpu| format!(", cpu {:.1?}", cpu.mean_duration()))
+            .unwrap_or_default();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        let heap = self
+            .heap
+            .map(|heap| format!(" {}", heap.summary()))
+            .unwrap_or_default();
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        if self.stats.samples == 1 {
+            format!(" ({duration:.1?}{cpu}){heap}")
+        } else {
+            format!(
+                " ({duration:.1?} @ {} samples{cpu}) {}{heap}",
+                self.stats.samples,
+                self.stats.summary()
+            )
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+///
+/// Wall-clock and thread CPU time are sampled for every run. Heap usage is recorded for the first run only, and
+/// only with the `heap-stats` feature.
+fn run_timed<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Measurement) {
+    let input_clone = input.clone();
+    let cpu_timer = CpuTimer::start();
     let timer = Instant::now();
-    let result = {
-        let input = input.clone();
-
+    let (result, heap) = heap::measure(|| {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
-    };
+        func(input_clone)
+    });
     let base_time = timer.elapsed();
+    let base_cpu_time = cpu_timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let (stats, cpu_stats) = if is_timed() {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        (
+            Stats::from_samples(&[base_time]),
+            base_cpu_time.map(|cpu_time| Stats::from_samples(&[cpu_time])),
+        )
     };
 
-    (result, run.0, run.1)
-}
+    let measurement = Measurement {
+        stats,
+        cpu_stats,
+        heap,
+    };
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+    (result, measurement)
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Bench a solution part, keeping every sample so the spread of timings can be reported.
+/// Returns the statistics of the wall-clock time and, if available, the thread CPU time.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Stats, Option<Stats>) {
+    if !is_quiet() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
+    let mut cpu_timers: Option<Vec<Duration>> = Some(vec![]);
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
+        let cpu_timer = CpuTimer::start();
         let timer = Instant::now();
         black_box(func(black_box(cloned)));
         timers.push(timer.elapsed());
+
+        // NOTE: a single failed reading of the CPU clock discards all CPU samples.
+        cpu_timers = cpu_timers
+            .zip(cpu_timer.elapsed())
+            .map(|(mut cpu_timers, cpu_time)| {
+                cpu_timers.push(cpu_time);
+                cpu_timers
+            });
     }
 
     (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+        Stats::from_samples(&timers),
+        cpu_timers.map(|cpu_timers| Stats::from_samples(&cpu_timers)),
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
-
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn print_result(outcome: &Outcome, part: &str, duration_str: &str) {
+    if is_quiet() {
+        return;
     }
-}
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+    match outcome {
+        Outcome::Answer(result) => {
+            if result.contains('\n') {
+                let decoded = ocr::decode(result)
+                    .map(|text| format!("{ANSI_BOLD}{text}{ANSI_RESET}"))
+                    .unwrap_or_default();
+                let str = format!("{part}: ▼ {decoded}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -118,7 +376,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,21 +384,27 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(reason) => {
+            let str = format!("{part}: ✖ {ANSI_RED}{reason}{ANSI_RESET}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}{duration_str}");
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session cookie is set up, or aoc-cli is installed.
+///  3. the answer is not provably wrong, based on previous submissions.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -156,14 +420,113 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answer = result.to_string();
+    let mut submissions = Submissions::read_from_file();
+
+    if let Err(refusal) = submissions.check(day, part, &answer) {
+        eprintln!("Refusing to submit \"{answer}\": {refusal}");
+        return;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let verdict = match AocClient::from_env() {
+        Ok(client) => {
+            println!("Submitting result...");
+            match client.submit(day, part, &answer) {
+                Ok(response) => {
+                    println!("{}", response.message);
+                    Some(response.verdict)
+                }
+                Err(e) => {
+                    eprintln!("Failed to submit result: {e}");
+                    None
+                }
+            }
+        }
+        // without a session cookie, leave authentication to aoc-cli.
+        Err(AocClientError::MissingSession) => {
+            if aoc_cli::check().is_err() {
+                eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+                process::exit(1);
+            }
+
+            println!("Submitting result via aoc-cli...");
+            match aoc_cli::submit(day, part, &answer) {
+                Ok(output) => Some(SubmissionVerdict::parse(&String::from_utf8_lossy(
+                    &output.stdout,
+                ))),
+                Err(e) => {
+                    eprintln!("Failed to submit result: {e}");
+                    None
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if let Some(verdict) = verdict {
+        submissions.record(day, part, &answer, verdict);
+
+        if let Err(e) = submissions.store_file() {
+            eprintln!("Failed to store submission: {e}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{agreed_outcome, Outcome, PartResult};
+    use anyhow::Context;
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).outcome(), Outcome::Answer("42".into()));
+        assert_eq!(None::<u32>.outcome(), Outcome::Unsolved);
+    }
+
+    #[test]
+    fn converts_results() {
+        let ok: Result<&str, String> = Ok("ABC");
+        assert_eq!(ok.outcome(), Outcome::Answer("ABC".into()));
+
+        let err: Result<u32, _> = "x".parse::<u32>();
+        assert_eq!(
+            err.outcome(),
+            Outcome::Failed("invalid digit found in string".into())
+        );
+    }
+
+    #[test]
+    fn prints_error_chains() {
+        let err: anyhow::Result<u32> = "x".parse::<u32>().context("line 3 is not a number");
+        assert_eq!(
+            err.outcome(),
+            Outcome::Failed("line 3 is not a number: invalid digit found in string".into())
+        );
+    }
+
+    #[test]
+    fn checks_that_variants_agree() {
+        let answer = Outcome::Answer("42".into());
+        assert_eq!(
+            agreed_outcome(&[("fast", answer.clone()), ("naive", answer.clone())]),
+            answer
+        );
+
+        assert_eq!(
+            agreed_outcome(&[
+                ("fast", answer),
+                ("naive", Outcome::Answer("43".into())),
+                ("broken", Outcome::Unsolved)
+            ]),
+            Outcome::Failed("variants disagree: fast = 42, naive = 43, broken = None".into())
+        );
+    }
 }
@@ -0,0 +1,290 @@
+/// Benchmarks how the run time of a solution grows with the size of its input.
+///
+/// Inputs of growing size come from the generator registered via `solution!(.., generator = ..)`, or from truncating
+/// the puzzle input by lines. Every size is benched the same way as `cargo time` benches the puzzle input, and a
+/// complexity class is fitted to the timings of each step.
+use std::fmt::Display;
+
+use crate::template::registry::Solution;
+use crate::template::report::{self, Report, Step};
+use crate::template::stats::Stats;
+use crate::template::{read_file, runner, ANSI_BOLD, ANSI_RESET};
+
+/// Sizes passed to a generator: 16, 32, …, 2048.
+const GENERATOR_SIZES: [usize; 8] = [16, 32, 64, 128, 256, 512, 1024, 2048];
+
+/// Seed passed to a generator, so that runs of the benchmark are comparable.
+const SEED: u64 = 0;
+
+/// How often the puzzle input is halved, i.e. the smallest input has 1/32 of its lines.
+const TRUNCATIONS: u32 = 5;
+
+/// Sizes are not grown further once a step takes longer than this on average (250ms).
+const SLOW_STEP_NANOS: f64 = 250_000_000_f64;
+
+/// A complexity class that the timings of a step can be fitted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    Linear,
+    Linearithmic,
+    Quadratic,
+}
+
+impl Complexity {
+    const ALL: [Self; 3] = [Self::Linear, Self::Linearithmic, Self::Quadratic];
+
+    /// Expected cost of an input of size `n`, up to a constant factor.
+    fn cost(self, n: f64) -> f64 {
+        match self {
+            Self::Linear => n,
+            Self::Linearithmic => n * (n + 1_f64).log2(),
+            Self::Quadratic => n * n,
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Linear => write!(f, "O(n)"),
+            Self::Linearithmic => write!(f, "O(n log n)"),
+            Self::Quadratic => write!(f, "O(n²)"),
+        }
+    }
+}
+
+/// The complexity class that fits the timings of a step best.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    pub complexity: Complexity,
+    /// Exponent `k` of the power law `n^k` that fits the timings best.
+    pub exponent: f64,
+}
+
+/// Fit a complexity class to `(size, nanoseconds)` pairs. Returns `None` for less than two distinct sizes.
+///
+/// Each class is fitted as `overhead + factor * cost(n)`, so that the constant overhead of small inputs does not
+/// distort the estimate. Errors are relative, so that small and large inputs weigh the same.
+pub fn fit(points: &[(usize, f64)]) -> Option<Fit> {
+    #[allow(clippy::cast_precision_loss)]
+    let mut points: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(n, nanos)| *n > 0 && *nanos > 0_f64)
+        .map(|&(n, nanos)| (n as f64, nanos))
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    // the exponent is estimated from the larger sizes, which are dominated less by overhead.
+    let exponent = log_slope(&points[points.len().saturating_sub(1) / 2..])?;
+
+    let residual = |complexity: Complexity| {
+        let (overhead, factor) = fit_linear(&points, complexity);
+        points
+            .iter()
+            .map(|(n, t)| ((overhead + factor * complexity.cost(*n) - t) / t).powi(2))
+            .sum::<f64>()
+    };
+
+    let complexity = Complexity::ALL
+        .into_iter()
+        .min_by(|a, b| residual(*a).total_cmp(&residual(*b)))?;
+
+    Some(Fit {
+        complexity,
+        exponent,
+    })
+}
+
+/// Slope of the timings on a log-log scale, i.e. `k` of the power law `n^k`. `None` without distinct sizes.
+fn log_slope(points: &[(f64, f64)]) -> Option<f64> {
+    #[allow(clippy::cast_precision_loss)]
+    let len = points.len() as f64;
+    let mean_log_n = points.iter().map(|(n, _)| n.ln()).sum::<f64>() / len;
+    let mean_log_t = points.iter().map(|(_, t)| t.ln()).sum::<f64>() / len;
+
+    let variance = points
+        .iter()
+        .map(|(n, _)| (n.ln() - mean_log_n).powi(2))
+        .sum::<f64>();
+
+    let covariance = points
+        .iter()
+        .map(|(n, t)| (n.ln() - mean_log_n) * (t.ln() - mean_log_t))
+        .sum::<f64>();
+
+    (variance > 0_f64).then(|| covariance / variance)
+}
+
+/// Weighted least squares fit of `overhead + factor * cost(n)`, with weights for relative errors.
+/// The overhead is never negative.
+fn fit_linear(points: &[(f64, f64)], complexity: Complexity) -> (f64, f64) {
+    let (mut sw, mut sf, mut sff, mut st, mut sft) = (0_f64, 0_f64, 0_f64, 0_f64, 0_f64);
+
+    for (n, t) in points {
+        let (w, f) = (t.powi(-2), complexity.cost(*n));
+        sw += w;
+        sf += w * f;
+        sff += w * f * f;
+        st += w * t;
+        sft += w * f * t;
+    }
+
+    let det = sw * sff - sf * sf;
+    let overhead = (st * sff - sf * sft) / det;
+
+    if det > 0_f64 && overhead >= 0_f64 {
+        (overhead, (sw * sft - sf * st) / det)
+    } else {
+        (0_f64, sft / sff)
+    }
+}
+
+/// Run a solution on inputs of growing size and print the timings of each step, followed by their complexity.
+pub fn run(solution: &Solution) {
+    // the puzzle input is only needed without a generator, and might not even be downloaded otherwise.
+    let lines: Vec<String> = match solution.generator {
+        Some(_) => vec![],
+        None => read_file("inputs", solution.day)
+            .lines()
+            .map(|line| format!("{line}\n"))
+            .collect(),
+    };
+
+    let (source, sizes) = match solution.generator {
+        Some(_) => ("generated inputs", GENERATOR_SIZES.to_vec()),
+        None => (
+            "puzzle input, truncated by lines",
+            truncated_sizes(lines.len()),
+        ),
+    };
+
+    println!(
+        "{ANSI_BOLD}Scaling of day {}{ANSI_RESET} ({source}):",
+        solution.day
+    );
+
+    runner::set_timed(true);
+    runner::set_quiet(true);
+
+    let mut steps: Vec<Step> = vec![];
+    let mut points: Vec<(usize, Vec<Report>)> = vec![];
+
+    for n in sizes {
+        let input = match solution.generator {
+            Some(generator) => generator(n, SEED),
+            None => lines[..n].concat(),
+        };
+
+        let reports = match report::collect_catching(|| (solution.run)(&input)) {
+            (reports, None) => reports,
+            (_, Some(message)) => {
+                println!("{n:>8}  panicked, skipping this size: {message}");
+                continue;
+            }
+        };
+
+        if steps.is_empty() {
+            steps = reports.iter().map(|r| r.step).collect();
+            let header: String = steps.iter().map(|step| format!("{step:>12}")).collect();
+            println!("{:>8}{header}", "n");
+        }
+
+        let row: String = steps
+            .iter()
+            .map(|step| {
+                let time = find_stats(&reports, *step)
+                    .map(|stats| format!("{:.1?}", stats.mean_duration()));
+                format!("{:>12}", time.unwrap_or_else(|| "-".into()))
+            })
+            .collect();
+        println!("{n:>8}{row}");
+
+        let is_slow = reports.iter().any(|r| r.stats.mean > SLOW_STEP_NANOS);
+        points.push((n, reports));
+
+        if is_slow {
+            println!("Stopping, as inputs of this size already take more than 250ms.");
+            break;
+        }
+    }
+
+    runner::set_quiet(false);
+
+    println!();
+    for step in steps {
+        let timings: Vec<(usize, f64)> = points
+            .iter()
+            .filter_map(|(n, reports)| Some((*n, find_stats(reports, step)?.mean)))
+            .collect();
+
+        match fit(&timings) {
+            Some(fit) => println!(
+                "{}: {ANSI_BOLD}{}{ANSI_RESET} (≈ n^{:.2})",
+                step, fit.complexity, fit.exponent
+            ),
+            None => println!("{}: not enough sizes to estimate", step),
+        }
+    }
+}
+
+/// Sizes of the truncated puzzle input, halving its number of lines each time. Ascending, without duplicates.
+fn truncated_sizes(lines: usize) -> Vec<usize> {
+    let mut sizes: Vec<usize> = (0..=TRUNCATIONS)
+        .rev()
+        .map(|k| lines >> k)
+        .filter(|&n| n > 0)
+        .collect();
+    sizes.dedup();
+    sizes
+}
+
+fn find_stats(reports: &[Report], step: Step) -> Option<&Stats> {
+    reports.iter().find(|r| r.step == step).map(|r| &r.stats)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fit, truncated_sizes, Complexity};
+
+    #[allow(clippy::cast_precision_loss)]
+    fn timings(cost: impl Fn(f64) -> f64) -> Vec<(usize, f64)> {
+        [100, 200, 400, 800, 1600, 3200]
+            .into_iter()
+            .map(|n| (n, cost(n as f64)))
+            .collect()
+    }
+
+    #[test]
+    fn fits_complexity_classes() {
+        let linear = fit(&timings(|n| 40_f64 * n + 500_f64)).unwrap();
+        assert_eq!(linear.complexity, Complexity::Linear);
+        assert!((linear.exponent - 1_f64).abs() < 0.1);
+
+        let linearithmic = fit(&timings(|n| 3_f64 * n * n.log2())).unwrap();
+        assert_eq!(linearithmic.complexity, Complexity::Linearithmic);
+
+        // overhead dominates the small sizes.
+        let quadratic = fit(&timings(|n| 0.01 * n * n + 2000_f64)).unwrap();
+        assert_eq!(quadratic.complexity, Complexity::Quadratic);
+
+        let quadratic = fit(&timings(|n| 0.5 * n * n)).unwrap();
+        assert_eq!(quadratic.complexity, Complexity::Quadratic);
+        assert!((quadratic.exponent - 2_f64).abs() < 0.1);
+    }
+
+    #[test]
+    fn needs_several_sizes() {
+        assert_eq!(fit(&[]), None);
+        assert_eq!(fit(&[(100, 1_f64)]), None);
+        assert_eq!(fit(&[(100, 1_f64), (100, 2_f64)]), None);
+    }
+
+    #[test]
+    fn halves_inputs() {
+        assert_eq!(truncated_sizes(140), vec![4, 8, 17, 35, 70, 140]);
+        assert_eq!(truncated_sizes(3), vec![1, 3]);
+        assert_eq!(truncated_sizes(0), Vec::<usize>::new());
+    }
+}
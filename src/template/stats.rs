@@ -0,0 +1,202 @@
+/// Summary statistics for the samples collected while benching a solution part.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Describes the distribution of a set of benchmark samples. All durations are stored as nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u64,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub max: f64,
+    pub std_dev: f64,
+    /// Number of samples outside of the Tukey fences (1.5 times the interquartile range).
+    pub outliers: u64,
+}
+
+impl Stats {
+    /// Computes statistics for a non-empty set of samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot compute stats without samples");
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (lower_fence, upper_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Stats {
+            samples: nanos.len() as u64,
+            min: nanos[0],
+            median: percentile(&nanos, 0.5),
+            mean,
+            p95: percentile(&nanos, 0.95),
+            max: nanos[nanos.len() - 1],
+            std_dev: variance.sqrt(),
+            outliers: nanos
+                .iter()
+                .filter(|&&x| x < lower_fence || x > upper_fence)
+                .count() as u64,
+        }
+    }
+
+    /// Mean sample duration.
+    pub fn mean_duration(&self) -> Duration {
+        to_duration(self.mean)
+    }
+
+    /// Formats the statistics in the form `[min 1.0µs, median 1.1µs, p95 1.5µs, max 2.0µs, σ 0.2µs, 3 outliers]`.
+    pub fn summary(&self) -> String {
+        format!(
+            "[min {:.1?}, median {:.1?}, p95 {:.1?}, max {:.1?}, σ {:.1?}, {} outliers]",
+            to_duration(self.min),
+            to_duration(self.median),
+            to_duration(self.p95),
+            to_duration(self.max),
+            to_duration(self.std_dev),
+            self.outliers
+        )
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/// Parse a duration formatted via `{:.1?}`, e.g. `1.2ms`, back to nanoseconds.
+pub fn parse_duration(time: &str) -> Option<f64> {
+    let units = [("ns", 1_f64), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)];
+
+    units.iter().find_map(|(unit, factor)| {
+        time.strip_suffix(unit)
+            .and_then(|value| value.parse::<f64>().ok())
+            .map(|value| value * factor)
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+            map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        }
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Stats {
+            samples: number("samples")? as u64,
+            min: number("min")?,
+            median: number("median")?,
+            mean: number("mean")?,
+            p95: number("p95")?,
+            max: number("max")?,
+            std_dev: number("std_dev")?,
+            outliers: number("outliers")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_duration, Stats};
+    use std::time::Duration;
+
+    fn get_mock_samples() -> Vec<Duration> {
+        let mut samples: Vec<Duration> = (1..=19).map(|x| Duration::from_nanos(100 + x)).collect();
+        samples.push(Duration::from_nanos(1000));
+        samples
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(&get_mock_samples());
+        assert_eq!(stats.samples, 20);
+        assert_eq!(stats.min, 101_f64);
+        assert_eq!(stats.median, 110_f64);
+        assert_eq!(stats.p95, 119_f64);
+        assert_eq!(stats.max, 1000_f64);
+        assert_eq!(stats.mean, 154.5_f64);
+        assert_eq!(stats.outliers, 1);
+        assert!((stats.std_dev - 194.04).abs() < 0.01);
+    }
+
+    #[test]
+    fn computes_stats_for_single_sample() {
+        let stats = Stats::from_samples(&[Duration::from_micros(3)]);
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.min, 3000_f64);
+        assert_eq!(stats.p95, 3000_f64);
+        assert_eq!(stats.std_dev, 0_f64);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn formats_summary() {
+        let stats = Stats::from_samples(&get_mock_samples());
+        assert_eq!(
+            stats.summary(),
+            "[min 101.0ns, median 110.0ns, p95 119.0ns, max 1.0µs, σ 194.0ns, 1 outliers]"
+        );
+    }
+
+    #[test]
+    fn parses_formatted_times() {
+        assert_eq!(parse_duration("75.0ns"), Some(75_f64));
+        assert_eq!(parse_duration("1.5µs"), Some(1_500_f64));
+        assert_eq!(parse_duration("74.1ms"), Some(74_100_000_f64));
+        assert_eq!(parse_duration("2.0s"), Some(2_000_000_000_f64));
+        assert_eq!(parse_duration("timed out"), None);
+    }
+}
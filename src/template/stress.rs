@@ -0,0 +1,175 @@
+/// Checks solutions against generated inputs and shrinks the inputs they fail on.
+///
+/// A solution fails on an input if it panics, if a part returns an error, or if the variants of a part disagree.
+use std::fmt::Display;
+
+use crate::template::registry::Solution;
+use crate::template::report::{self, Step};
+use crate::template::runner;
+
+/// Why a solution failed on an input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FailureKind {
+    Panic(String),
+    /// A part returned an error, or its variants disagreed.
+    Error {
+        step: Step,
+        message: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Failure {
+    /// Number of steps that completed before the failure.
+    pub completed: usize,
+    pub kind: FailureKind,
+}
+
+impl Failure {
+    /// Whether `other` is the same bug, i.e. the same kind of failure in the same step. Used when shrinking inputs,
+    /// which must not turn e.g. a wrong answer into a parse error.
+    pub fn is_like(&self, other: &Failure) -> bool {
+        let is_same_kind = matches!(
+            (&self.kind, &other.kind),
+            (FailureKind::Panic(_), FailureKind::Panic(_))
+                | (FailureKind::Error { .. }, FailureKind::Error { .. })
+        );
+
+        is_same_kind && self.completed == other.completed
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            FailureKind::Panic(message) => write!(f, "panicked: {message}"),
+            FailureKind::Error { step, message } => write!(f, "{step}: {message}"),
+        }
+    }
+}
+
+/// Run a solution once on `input`, without printing its results. Returns how it failed, if it did.
+pub fn check(solution: &Solution, input: &str) -> Option<Failure> {
+    runner::set_timed(false);
+    runner::set_quiet(true);
+    let (reports, panic) = report::collect_catching(|| (solution.run)(input));
+    runner::set_quiet(false);
+
+    if let Some((completed, report)) = reports
+        .iter()
+        .enumerate()
+        .find(|(_, report)| report.error.is_some())
+    {
+        return Some(Failure {
+            completed,
+            kind: FailureKind::Error {
+                step: report.step,
+                message: report.error.clone().unwrap_or_default(),
+            },
+        });
+    }
+
+    panic.map(|message| Failure {
+        completed: reports.len(),
+        kind: FailureKind::Panic(message),
+    })
+}
+
+/// Shrink a failing input by removing lines, as long as it still fails. Removes large chunks of lines first, then
+/// smaller ones down to single lines. Gives up after `max_attempts` runs of `fails`.
+pub fn shrink(input: &str, mut fails: impl FnMut(&str) -> bool, max_attempts: usize) -> String {
+    let join = |lines: &[&str]| {
+        let mut joined = lines.join("\n");
+        if input.ends_with('\n') {
+            joined.push('\n');
+        }
+        joined
+    };
+
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = lines.len() / 2;
+    let mut attempts = 0;
+
+    while chunk > 0 && attempts < max_attempts {
+        let mut start = 0;
+        let mut has_removed = false;
+
+        while start < lines.len() && attempts < max_attempts {
+            let end = (start + chunk).min(lines.len());
+            let candidate: Vec<&str> = [&lines[..start], &lines[end..]].concat();
+            attempts += 1;
+
+            if !candidate.is_empty() && fails(&join(&candidate)) {
+                lines = candidate;
+                has_removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+
+        // retry the same chunk size while it makes progress.
+        if !has_removed {
+            chunk /= 2;
+        }
+    }
+
+    join(&lines)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{shrink, Failure, FailureKind};
+    use crate::template::report::Step;
+
+    #[test]
+    fn shrinks_to_the_failing_lines() {
+        let input: String = (0..100).map(|i| format!("{i}\n")).collect();
+
+        // fails whenever the input contains both 17 and 42.
+        let fails = |input: &str| {
+            let lines: Vec<&str> = input.lines().collect();
+            lines.contains(&"17") && lines.contains(&"42")
+        };
+
+        assert_eq!(shrink(&input, fails, 1000), "17\n42\n");
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let input: String = (0..100).map(|i| format!("{i}\n")).collect();
+        let mut attempts = 0;
+
+        let shrunk = shrink(
+            &input,
+            |_| {
+                attempts += 1;
+                false
+            },
+            10,
+        );
+
+        assert_eq!(attempts, 10);
+        assert_eq!(shrunk, input);
+    }
+
+    #[test]
+    fn compares_failures() {
+        let disagreement = |completed, answer: &str| Failure {
+            completed,
+            kind: FailureKind::Error {
+                step: Step::Part(1),
+                message: format!("variants disagree: {answer}"),
+            },
+        };
+        let panic = Failure {
+            completed: 1,
+            kind: FailureKind::Panic("index out of bounds".into()),
+        };
+
+        assert!(disagreement(1, "a = 1, b = 2").is_like(&disagreement(1, "a = 3, b = 4")));
+        assert!(!disagreement(1, "a = 1, b = 2").is_like(&disagreement(0, "a = 1, b = 2")));
+        assert!(!disagreement(1, "a = 1, b = 2").is_like(&panic));
+    }
+}
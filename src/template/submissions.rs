@@ -0,0 +1,432 @@
+/// History of submitted answers, used to refuse submissions that are known to be wrong.
+///
+/// Every submission and the verdict of the Advent of Code server are stored in `data/submissions.json`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, Day};
+
+/// Submissions are kept per year, see [`data_dir`].
+fn submissions_path() -> PathBuf {
+    data_dir().join("submissions.json")
+}
+
+/// Verdict of the Advent of Code server for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionVerdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was submitted too soon after a previous one and was not checked.
+    RateLimited,
+    /// The response could not be understood.
+    Unknown,
+}
+
+impl SubmissionVerdict {
+    /// Parse the verdict from the response to a submission, as printed by aoc-cli.
+    pub fn parse(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            SubmissionVerdict::Correct
+        } else if response.contains("You gave an answer too recently") {
+            SubmissionVerdict::RateLimited
+        } else if response.contains("your answer is too high") {
+            SubmissionVerdict::TooHigh
+        } else if response.contains("your answer is too low") {
+            SubmissionVerdict::TooLow
+        } else if response.contains("That's not the right answer") {
+            SubmissionVerdict::Wrong
+        } else {
+            SubmissionVerdict::Unknown
+        }
+    }
+
+    /// Whether the server checked the answer, i.e. whether a repeated submission would get the same verdict.
+    fn is_final(self) -> bool {
+        !matches!(
+            self,
+            SubmissionVerdict::RateLimited | SubmissionVerdict::Unknown
+        )
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            SubmissionVerdict::Correct => "correct",
+            SubmissionVerdict::Wrong => "wrong",
+            SubmissionVerdict::TooHigh => "too_high",
+            SubmissionVerdict::TooLow => "too_low",
+            SubmissionVerdict::RateLimited => "rate_limited",
+            SubmissionVerdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for SubmissionVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str().replace('_', " "))
+    }
+}
+
+impl FromStr for SubmissionVerdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            SubmissionVerdict::Correct,
+            SubmissionVerdict::Wrong,
+            SubmissionVerdict::TooHigh,
+            SubmissionVerdict::TooLow,
+            SubmissionVerdict::RateLimited,
+            SubmissionVerdict::Unknown,
+        ]
+        .into_iter()
+        .find(|v| v.as_str() == s)
+        .ok_or(format!("`{s}` is not a verdict."))
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub answer: String,
+    pub verdict: SubmissionVerdict,
+    /// Unix timestamp (in seconds) of the submission.
+    pub submitted_at: u64,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    Repeated(SubmissionVerdict),
+    TooHigh(String),
+    TooLow(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with \"{answer}\".")
+            }
+            Refusal::Repeated(verdict) => {
+                write!(f, "this answer was already submitted and was {verdict}.")
+            }
+            Refusal::TooHigh(bound) => {
+                write!(
+                    f,
+                    "\"{bound}\" was already too high, so this answer is too high as well."
+                )
+            }
+            Refusal::TooLow(bound) => {
+                write!(
+                    f,
+                    "\"{bound}\" was already too low, so this answer is too low as well."
+                )
+            }
+        }
+    }
+}
+
+/// Submissions per day and part, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    data: BTreeMap<(Day, u8), Vec<Submission>>,
+}
+
+impl Submissions {
+    /// Rehydrate submissions from a JSON file. If not present, returns no submissions.
+    pub fn read_from_file() -> Self {
+        let path = submissions_path();
+        let s = match fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Submissions::default(),
+            Err(e) => {
+                eprintln!("{e}");
+                return Submissions::default();
+            }
+        };
+
+        Submissions::try_from(s).unwrap_or_else(|e| {
+            eprintln!("Failed to read \"{}\": {e}", path.display());
+            Submissions::default()
+        })
+    }
+
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(submissions_path())?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> &[Submission] {
+        self.data.get(&(day, part)).map_or(&[], Vec::as_slice)
+    }
+
+    /// Record a submission made now.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: SubmissionVerdict) {
+        let submitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.entry((day, part)).or_default().push(Submission {
+            answer: answer.to_string(),
+            verdict,
+            submitted_at,
+        });
+    }
+
+    /// Check whether an answer may be submitted, i.e. whether it is not provably wrong given the previous submissions.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let submissions = self.get(day, part);
+
+        if let Some(correct) = submissions
+            .iter()
+            .find(|s| s.verdict == SubmissionVerdict::Correct)
+        {
+            return Err(Refusal::AlreadySolved(correct.answer.clone()));
+        }
+
+        if let Some(repeat) = submissions
+            .iter()
+            .find(|s| s.answer == answer && s.verdict.is_final())
+        {
+            return Err(Refusal::Repeated(repeat.verdict));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: SubmissionVerdict| {
+            submissions
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, &s.answer)))
+        };
+
+        if let Some((_, too_high)) = bound(SubmissionVerdict::TooHigh)
+            .filter(|(bound, _)| value >= *bound)
+            .min()
+        {
+            return Err(Refusal::TooHigh(too_high.clone()));
+        }
+
+        if let Some((_, too_low)) = bound(SubmissionVerdict::TooLow)
+            .filter(|(bound, _)| value <= *bound)
+            .max()
+        {
+            return Err(Refusal::TooLow(too_low.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut days: HashMap<String, HashMap<String, JsonValue>> = HashMap::new();
+
+        for ((day, part), submissions) in &value.data {
+            let submissions = submissions
+                .iter()
+                .map(|s| {
+                    let mut map: HashMap<String, JsonValue> = HashMap::new();
+                    map.insert("answer".into(), JsonValue::String(s.answer.clone()));
+                    map.insert(
+                        "verdict".into(),
+                        JsonValue::String(s.verdict.as_str().into()),
+                    );
+                    #[allow(clippy::cast_precision_loss)]
+                    map.insert(
+                        "submitted_at".into(),
+                        JsonValue::Number(s.submitted_at as f64),
+                    );
+                    JsonValue::Object(map)
+                })
+                .collect();
+
+            days.entry(day.to_string())
+                .or_default()
+                .insert(part.to_string(), JsonValue::Array(submissions));
+        }
+
+        JsonValue::Object(
+            days.into_iter()
+                .map(|(day, parts)| (day, JsonValue::Object(parts)))
+                .collect(),
+        )
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let submitted_at = json
+            .get("submitted_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected submission.submitted_at to be a number.")?;
+
+        Ok(Submission {
+            answer: answer.clone(),
+            verdict,
+            submitted_at,
+        })
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut submissions = Submissions::default();
+
+        for (day, parts) in days {
+            let day = Day::from_str(day).or(Err(format!("expected `{day}` to be a day.")))?;
+
+            let parts = parts.get::<HashMap<String, JsonValue>>().ok_or(format!(
+                "expected submissions of day {day} to be an object."
+            ))?;
+
+            for (part, entries) in parts {
+                let part = match part.parse::<u8>() {
+                    Ok(part @ 1..=2) => part,
+                    _ => return Err(format!("expected `{part}` of day {day} to be a part.")),
+                };
+
+                let entries = entries
+                    .get::<Vec<JsonValue>>()
+                    .ok_or(format!(
+                        "expected submissions of day {day}, part {part} to be an array."
+                    ))?
+                    .iter()
+                    .map(Submission::try_from)
+                    .collect::<Result<_, _>>()?;
+
+                submissions.data.insert((day, part), entries);
+            }
+        }
+
+        Ok(submissions)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Refusal, SubmissionVerdict, Submissions};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "100", SubmissionVerdict::TooHigh);
+        submissions.record(day!(1), 1, "20", SubmissionVerdict::TooLow);
+        submissions.record(day!(1), 1, "50", SubmissionVerdict::Wrong);
+        submissions.record(day!(1), 1, "60", SubmissionVerdict::RateLimited);
+        submissions.record(day!(1), 2, "abc", SubmissionVerdict::Correct);
+        submissions
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            SubmissionVerdict::parse("That's the right answer! You are one gold star closer."),
+            SubmissionVerdict::Correct
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("That's not the right answer; your answer is too high."),
+            SubmissionVerdict::TooHigh
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("That's not the right answer; your answer is too low."),
+            SubmissionVerdict::TooLow
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("That's not the right answer. If you're stuck, ..."),
+            SubmissionVerdict::Wrong
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("You gave an answer too recently; you have 30s left to wait."),
+            SubmissionVerdict::RateLimited
+        );
+        assert_eq!(SubmissionVerdict::parse(""), SubmissionVerdict::Unknown);
+    }
+
+    #[test]
+    fn refuses_provably_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "50"),
+            Err(Refusal::Repeated(SubmissionVerdict::Wrong))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "100"),
+            Err(Refusal::Repeated(SubmissionVerdict::TooHigh))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "150"),
+            Err(Refusal::TooHigh("100".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "3"),
+            Err(Refusal::TooLow("20".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 2, "abd"),
+            Err(Refusal::AlreadySolved("abc".into()))
+        );
+    }
+
+    #[test]
+    fn allows_plausible_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(1), 1, "42"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "60"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "xyz"), Ok(()));
+        assert_eq!(submissions.check(day!(2), 1, "1"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = get_mock_submissions();
+        let json = JsonValue::from(&submissions).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap(), submissions);
+    }
+}
@@ -0,0 +1,143 @@
+/// Templates that `cargo scaffold` creates solutions from.
+///
+/// The built-in template is `src/template.txt`. Further templates live in the `templates` directory and are picked by
+/// their file name, e.g. `cargo scaffold 1 --template grid` uses `templates/grid.txt`.
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::template::{Day, Year};
+
+pub const DEFAULT_NAME: &str = "default";
+
+const DEFAULT_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+const TEMPLATES_DIR: &str = "templates";
+
+#[derive(Debug)]
+pub enum TemplateError {
+    NotFound {
+        name: String,
+        available: Vec<String>,
+    },
+    IO(io::Error),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::NotFound { name, available } => write!(
+                f,
+                "template \"{name}\" not found. Available templates: {}.",
+                available.join(", ")
+            ),
+            TemplateError::IO(e) => write!(f, "failed to read template: {e}"),
+        }
+    }
+}
+
+/// Names of all templates, starting with the default one.
+pub fn names() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .filter(|name| name != DEFAULT_NAME)
+        .collect();
+
+    names.sort();
+    names.insert(0, DEFAULT_NAME.to_string());
+    names
+}
+
+/// Read the template called `name`. A `templates/default.txt` replaces the built-in template.
+pub fn load(name: &str) -> Result<String, TemplateError> {
+    let path = path_for(name);
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name == DEFAULT_NAME => Ok(DEFAULT_TEMPLATE.to_string()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(TemplateError::NotFound {
+            name: name.to_string(),
+            available: names(),
+        }),
+        Err(e) => Err(TemplateError::IO(e)),
+    }
+}
+
+fn path_for(name: &str) -> PathBuf {
+    Path::new(TEMPLATES_DIR).join(format!("{name}.txt"))
+}
+
+/// Replace the placeholders of a template:
+///
+/// - `%DAY_NUMBER%`: the day without padding, e.g. `1`.
+/// - `%DAY_PADDED%`: the day padded to two digits, e.g. `01`.
+/// - `%YEAR%`: the selected year, e.g. `2023`.
+/// - `%TITLE%`: the title of the puzzle, e.g. `Trebuchet?!`, or `untitled` if the puzzle is not downloaded.
+pub fn fill(template: &str, day: Day, year: Option<Year>, title: Option<&str>) -> String {
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY_PADDED%", &day.to_string())
+        .replace("%YEAR%", &year.map(|y| y.to_string()).unwrap_or_default())
+        .replace("%TITLE%", title.unwrap_or("untitled"))
+}
+
+/// Read the title from a downloaded puzzle description, i.e. from its `## --- Day 1: Trebuchet?! ---` heading.
+pub fn parse_puzzle_title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let heading = line
+            .trim()
+            .strip_prefix("## --- Day ")?
+            .strip_suffix(" ---")?;
+        let (_, title) = heading.split_once(": ")?;
+        Some(title.trim().to_string())
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fill, load, parse_puzzle_title, TemplateError, DEFAULT_NAME, DEFAULT_TEMPLATE};
+    use crate::template::{Day, Year};
+
+    #[test]
+    fn fills_placeholders() {
+        let template = "// %YEAR% day %DAY_PADDED%: %TITLE%\nsolution!(%DAY_NUMBER%);";
+        let day = Day::new(4).unwrap();
+
+        assert_eq!(
+            fill(template, day, Year::new(2023), Some("Scratchcards")),
+            "// 2023 day 04: Scratchcards\nsolution!(4);"
+        );
+        assert_eq!(
+            fill(template, day, None, None),
+            "//  day 04: untitled\nsolution!(4);"
+        );
+    }
+
+    #[test]
+    fn parses_puzzle_title() {
+        let markdown =
+            "\n## --- Day 1: Trebuchet?! ---\n\nSomething is wrong with global snow production.";
+
+        assert_eq!(parse_puzzle_title(markdown), Some("Trebuchet?!".into()));
+        assert_eq!(parse_puzzle_title("no heading"), None);
+    }
+
+    #[test]
+    fn loads_templates() {
+        assert_eq!(load(DEFAULT_NAME).unwrap(), DEFAULT_TEMPLATE);
+        assert!(load("grid").unwrap().contains("%DAY_NUMBER%"));
+        assert!(matches!(
+            load("does-not-exist"),
+            Err(TemplateError::NotFound { available, .. }) if available.contains(&"grid".to_string())
+        ));
+    }
+}
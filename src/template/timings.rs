@@ -1,20 +1,78 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    path::PathBuf,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{data_dir, heap::HeapStats, stats::Stats, Day};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// Timings are kept per year, see [`data_dir`].
+fn timings_path() -> PathBuf {
+    data_dir().join("timings.json")
+}
 
-/// Represents benchmark times for a single day.
+/// Represents benchmark times of a single run of a day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Thread CPU time, which is less affected by other load on the machine than wall-clock time.
+    pub parse_cpu_stats: Option<Stats>,
+    pub part_1_cpu_stats: Option<Stats>,
+    pub part_2_cpu_stats: Option<Stats>,
+    /// Heap usage, only recorded with the `heap-stats` feature.
+    pub parse_heap: Option<HeapStats>,
+    pub part_1_heap: Option<HeapStats>,
+    pub part_2_heap: Option<HeapStats>,
     pub total_nanos: f64,
+    /// Whether the solution was killed after running into a timeout.
+    pub timed_out: bool,
+    /// Unix timestamp (in seconds) of when the run was stored.
+    pub recorded_at: Option<u64>,
+    /// Short hash of the git commit the run was stored at.
+    pub commit: Option<String>,
 }
 
-/// Represents benchmark times for a set of days.
+impl Timing {
+    /// A timing of `day` without any results.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_cpu_stats: None,
+            part_1_cpu_stats: None,
+            part_2_cpu_stats: None,
+            parse_heap: None,
+            part_1_heap: None,
+            part_2_heap: None,
+            total_nanos: 0_f64,
+            timed_out: false,
+            recorded_at: None,
+            commit: None,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.part_1.is_some() && self.part_2.is_some()
+    }
+}
+
+/// Represents the benchmark history for a set of days, oldest run first for every day.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
@@ -24,14 +82,31 @@ pub struct Timings {
 impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
+        self.store_to(&timings_path())
+    }
+
+    /// Dehydrate timings to the file of a named baseline.
+    pub fn store_baseline(&self, name: &str) -> Result<(), Error> {
+        self.store_to(&baseline_path(name))
+    }
+
+    /// Rehydrate timings from the file of a named baseline.
+    pub fn read_baseline(name: &str) -> Result<Self, String> {
+        let path = baseline_path(name);
+        let s = fs::read_to_string(&path)
+            .map_err(|e| format!("could not read baseline \"{}\": {e}", path.display()))?;
+        Timings::try_from(s)
+    }
+
+    fn store_to(&self, path: &PathBuf) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+        let s = fs::read_to_string(timings_path())
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -44,36 +119,114 @@ impl Timings {
         }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Append the runs of `new` to the history. Queries such as [`Timings::latest`] then return them
+    /// in favor of the runs in `self`.
     pub fn merge(&self, new: &Self) -> Self {
-        let mut data: Vec<Timing> = vec![];
+        let mut data: Vec<Timing> = self.data.iter().chain(&new.data).cloned().collect();
+
+        // NOTE: the sort is stable, so the runs of every day stay in chronological order.
+        data.sort_by_key(|t| t.day);
+        Timings { data }
+    }
 
-        for timing in &new.data {
-            data.push(timing.clone());
+    /// Mark all runs as recorded now, at the current git commit.
+    #[must_use]
+    pub fn stamp(mut self) -> Self {
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .ok();
+        let commit = current_commit();
+
+        for timing in &mut self.data {
+            timing.recorded_at = recorded_at;
+            timing.commit.clone_from(&commit);
         }
 
-        for timing in &self.data {
+        self
+    }
+
+    /// All runs of a day, oldest first.
+    pub fn history(&self, day: Day) -> impl DoubleEndedIterator<Item = &Timing> {
+        self.data.iter().filter(move |t| t.day == day)
+    }
+
+    /// The most recent run of a day.
+    pub fn latest_for(&self, day: Day) -> Option<&Timing> {
+        self.history(day).last()
+    }
+
+    /// The most recent run of every day.
+    pub fn latest(&self) -> Self {
+        let mut data: Vec<Timing> = vec![];
+
+        for timing in self.data.iter().rev() {
             if !data.iter().any(|t| t.day == timing.day) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
+    /// The fastest run of a day that solved both parts.
+    pub fn best(&self, day: Day) -> Option<&Timing> {
+        self.history(day)
+            .filter(|t| t.is_complete() && !t.timed_out)
+            .min_by(|a, b| a.total_nanos.total_cmp(&b.total_nanos))
+    }
+
+    /// Change of the total time of the most recent run of a day compared to the run before, in percent.
+    /// Positive values mean the day got slower.
+    pub fn trend(&self, day: Day) -> Option<f64> {
+        let mut history = self.history(day).rev();
+        let latest = history.next()?;
+        let previous = history.next()?;
+
+        if previous.total_nanos == 0_f64 {
+            return None;
+        }
+
+        Some((latest.total_nanos - previous.total_nanos) / previous.total_nanos * 100_f64)
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.latest_for(day).is_some_and(Timing::is_complete)
     }
 }
 
+/// Whether `name` can be used as a baseline name. Baselines are stored as files, so only
+/// alphanumeric characters, `-` and `_` are allowed.
+pub fn is_valid_baseline_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Baselines are stored next to the main timings, e.g. `data/timings.naive.json`.
+fn baseline_path(name: &str) -> PathBuf {
+    timings_path().with_extension(format!("{name}.json"))
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -121,6 +274,10 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -140,6 +297,55 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if value.timed_out {
+            map.insert("timed_out".into(), JsonValue::Boolean(true));
+        }
+
+        if let Some(recorded_at) = value.recorded_at {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("recorded_at".into(), JsonValue::Number(recorded_at as f64));
+        }
+
+        if let Some(commit) = &value.commit {
+            map.insert("commit".into(), JsonValue::String(commit.clone()));
+        }
+
+        if let Some(stats) = &value.parse_stats {
+            map.insert("parse_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_1_stats {
+            map.insert("part_1_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_2_stats {
+            map.insert("part_2_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.parse_cpu_stats {
+            map.insert("parse_cpu_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_1_cpu_stats {
+            map.insert("part_1_cpu_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_2_cpu_stats {
+            map.insert("part_2_cpu_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(heap) = &value.parse_heap {
+            map.insert("parse_heap".into(), JsonValue::from(heap));
+        }
+
+        if let Some(heap) = &value.part_1_heap {
+            map.insert("part_1_heap".into(), JsonValue::from(heap));
+        }
+
+        if let Some(heap) = &value.part_2_heap {
+            map.insert("part_2_heap".into(), JsonValue::from(heap));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -158,6 +364,9 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // NOTE: `parse` is only present for solutions with a separate parser.
+        let parse = json.get("parse").and_then(|v| v.get::<String>());
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -173,11 +382,51 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: stats are optional to keep timings stored by older versions readable.
+        let parse_stats = json.get("parse_stats").map(Stats::try_from).transpose()?;
+        let part_1_stats = json.get("part_1_stats").map(Stats::try_from).transpose()?;
+        let part_2_stats = json.get("part_2_stats").map(Stats::try_from).transpose()?;
+
+        let parse_cpu_stats = json.get("parse_cpu_stats").map(Stats::try_from).transpose()?;
+        let part_1_cpu_stats = json.get("part_1_cpu_stats").map(Stats::try_from).transpose()?;
+        let part_2_cpu_stats = json.get("part_2_cpu_stats").map(Stats::try_from).transpose()?;
+
+        let parse_heap = json.get("parse_heap").map(HeapStats::try_from).transpose()?;
+        let part_1_heap = json.get("part_1_heap").map(HeapStats::try_from).transpose()?;
+        let part_2_heap = json.get("part_2_heap").map(HeapStats::try_from).transpose()?;
+
+        let timed_out = json
+            .get("timed_out")
+            .and_then(|v| v.get::<bool>().copied())
+            .unwrap_or(false);
+
+        // NOTE: runs stored by older versions have no run info.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let recorded_at = json
+            .get("recorded_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64);
+
+        let commit = json.get("commit").and_then(|v| v.get::<String>());
+
         Ok(Timing {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            parse_cpu_stats,
+            part_1_cpu_stats,
+            part_2_cpu_stats,
+            parse_heap,
+            part_1_heap,
+            part_2_heap,
             total_nanos,
+            timed_out,
+            recorded_at,
+            commit: commit.cloned(),
         })
     }
 }
@@ -194,22 +443,21 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -227,9 +475,42 @@ mod tests {
             assert_eq!(timing.day, day!(1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
+            assert_eq!(timing.parse, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_parse() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2ms", "part_1": "1ms", "part_2": "1ms", "total_nanos": 4000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("2ms".to_string()));
+            assert_eq!(timing.part_1, Some("1ms".to_string()));
+        }
+
+        #[test]
+        fn handles_timed_out_timings() {
+            let json = r#"{ "data": [{ "day": "05", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "timed_out": true }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.timed_out, true);
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn handles_timing_history() {
+            let json = r#"{ "data": [
+                { "day": "01", "part_1": "2ms", "part_2": null, "total_nanos": 2000000, "recorded_at": 1700000000, "commit": "abc1234" },
+                { "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "recorded_at": 1700000100, "commit": "def5678" }
+            ] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 2);
+            let latest = timings.latest_for(day!(1)).unwrap();
+            assert_eq!(latest.recorded_at, Some(1_700_000_100));
+            assert_eq!(latest.commit, Some("def5678".to_string()));
+            assert_eq!(latest.part_1, Some("1ms".to_string()));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -275,6 +556,84 @@ mod tests {
         }
     }
 
+    mod baselines {
+        use crate::template::timings::{baseline_path, is_valid_baseline_name};
+        use std::path::PathBuf;
+
+        #[test]
+        fn stores_baselines_next_to_timings() {
+            assert_eq!(
+                baseline_path("naive"),
+                PathBuf::from("data/timings.naive.json")
+            );
+        }
+
+        #[test]
+        fn validates_baseline_names() {
+            assert_eq!(is_valid_baseline_name("naive"), true);
+            assert_eq!(is_valid_baseline_name("bfs_v2-fast"), true);
+            assert_eq!(is_valid_baseline_name(""), false);
+            assert_eq!(is_valid_baseline_name("../timings"), false);
+            assert_eq!(is_valid_baseline_name("a.b"), false);
+        }
+    }
+
+    mod history {
+        use crate::{
+            day,
+            template::timings::{Timing, Timings},
+        };
+
+        fn get_mock_run(part_2: Option<&str>, total_nanos: f64) -> Timing {
+            Timing {
+                part_1: Some("1ms".into()),
+                part_2: part_2.map(String::from),
+                total_nanos,
+                ..Timing::new(day!(1))
+            }
+        }
+
+        fn get_mock_history() -> Timings {
+            Timings {
+                data: vec![
+                    get_mock_run(Some("2ms"), 3_000_000_f64),
+                    get_mock_run(None, 1_000_000_f64),
+                    get_mock_run(Some("1ms"), 2_000_000_f64),
+                    get_mock_run(Some("2ms"), 3_000_000_f64),
+                ],
+            }
+        }
+
+        #[test]
+        fn finds_latest_run() {
+            let timings = get_mock_history();
+            assert_eq!(timings.latest_for(day!(1)).unwrap().total_nanos, 3_000_000_f64);
+            assert_eq!(timings.latest().data.len(), 1);
+            assert_eq!(timings.latest_for(day!(2)).is_none(), true);
+        }
+
+        #[test]
+        fn finds_best_complete_run() {
+            let timings = get_mock_history();
+            assert_eq!(timings.best(day!(1)).unwrap().total_nanos, 2_000_000_f64);
+        }
+
+        #[test]
+        fn computes_trend() {
+            let timings = get_mock_history();
+            assert_eq!(timings.trend(day!(1)), Some(50_f64));
+            assert_eq!(timings.latest().trend(day!(1)), None);
+        }
+
+        #[test]
+        fn checks_completion_of_latest_run() {
+            let mut timings = get_mock_history();
+            assert_eq!(timings.is_day_complete(day!(1)), true);
+            timings.data.push(get_mock_run(None, 1_000_000_f64));
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
     mod is_day_complete {
         use crate::{
             day,
@@ -285,42 +644,36 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -336,12 +689,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -356,20 +704,20 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 
-            assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.history(day!(2)).count(), 2);
+            assert_eq!(merged.latest_for(day!(2)).unwrap().total_nanos, 0_f64);
+
+            let latest = merged.latest();
+            assert_eq!(latest.data.len(), 3);
+            assert_eq!(latest.data[0].day, day!(1));
+            assert_eq!(latest.data[1].day, day!(2));
+            assert_eq!(latest.data[1].total_nanos, 0_f64);
+            assert_eq!(latest.data[2].day, day!(4));
         }
 
         #[test]
@@ -0,0 +1,165 @@
+/// Re-runs a solution whenever its source or data files change, see `cargo solve <day> --watch`.
+///
+/// Files are watched by polling their modification times, which needs no platform-specific file system events.
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::{bin_name, data_dir, Day};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Files that affect the solution of a day: its binary, the library and the day's input and examples.
+pub fn watched_files(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(format!("src/bin/{}.rs", bin_name(day))),
+        PathBuf::from("src/lib.rs"),
+        data_dir().join("inputs").join(format!("{day}.txt")),
+    ];
+
+    // examples of single parts, e.g. `01-2.txt`, count as well.
+    let examples_dir = data_dir().join("examples");
+    if let Ok(entries) = fs::read_dir(&examples_dir) {
+        let mut examples: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&day.to_string()))
+            })
+            .collect();
+        examples.sort();
+        files.extend(examples);
+    }
+
+    files
+}
+
+/// Call `on_change` once, then again whenever one of the files returned by `files` is changed, created or removed.
+/// Runs until the process is stopped.
+pub fn watch(files: impl Fn() -> Vec<PathBuf>, mut on_change: impl FnMut()) -> ! {
+    let mut last_snapshot = None;
+
+    loop {
+        let snapshot: Vec<(PathBuf, Option<SystemTime>)> = files()
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, modified)
+            })
+            .collect();
+
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            last_snapshot = Some(snapshot);
+            on_change();
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Outcome of a single test, as printed by the test harness.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+    /// What the test printed before failing, e.g. the failed assertion.
+    pub output: Vec<String>,
+}
+
+/// Parse the human-readable output of the test harness. Ignored tests are omitted.
+pub fn parse_test_output(stdout: &str) -> Vec<TestResult> {
+    let mut results: Vec<TestResult> = stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, status) = line.strip_prefix("test ")?.split_once(" ... ")?;
+            let passed = match status {
+                "ok" => true,
+                "FAILED" => false,
+                _ => return None,
+            };
+
+            Some(TestResult {
+                name: name.to_string(),
+                passed,
+                output: vec![],
+            })
+        })
+        .collect();
+
+    // the output of failed tests follows in sections like `---- tests::test_part_one stdout ----`.
+    let mut current: Option<&mut TestResult> = None;
+
+    for line in stdout.lines() {
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|l| l.strip_suffix(" stdout ----"))
+        {
+            current = results.iter_mut().find(|result| result.name == name);
+        } else if line == "failures:" {
+            current = None;
+        } else if let Some(result) = current.as_mut() {
+            if !line.trim().is_empty() && !line.starts_with("note: run with `RUST_BACKTRACE") {
+                result.output.push(line.to_string());
+            }
+        }
+    }
+
+    results
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_test_output, TestResult};
+
+    #[test]
+    fn parses_test_output() {
+        let stdout = "
+running 3 tests
+test tests::test_part_one ... ok
+test tests::test_part_two ... FAILED
+test tests::test_slow ... ignored
+
+failures:
+
+---- tests::test_part_two stdout ----
+
+thread 'tests::test_part_two' panicked at src/bin/04.rs:95:9:
+assertion `left == right` failed
+  left: Some(30)
+ right: Some(31)
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::test_part_two
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+        assert_eq!(
+            parse_test_output(stdout),
+            vec![
+                TestResult {
+                    name: "tests::test_part_one".into(),
+                    passed: true,
+                    output: vec![]
+                },
+                TestResult {
+                    name: "tests::test_part_two".into(),
+                    passed: false,
+                    output: vec![
+                        "thread 'tests::test_part_two' panicked at src/bin/04.rs:95:9:".into(),
+                        "assertion `left == right` failed".into(),
+                        "  left: Some(30)".into(),
+                        " right: Some(31)".into(),
+                    ]
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+const FIRST_YEAR: u16 = 2015;
+
+/// A year of advent (i.e. 2015 or later).
+///
+/// A year either keeps its solutions and data in the flat layout of the template (`src/bin/01.rs`,
+/// `data/inputs/01.txt`), or in its own directory (`src/bin/2022-01.rs`, `data/2022/inputs/01.txt`) if
+/// `data/<year>` exists. This allows to keep several years in one repository.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year: Year = "2022".parse().unwrap();
+/// assert_eq!(year.to_string(), "2022")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of advent,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        (year >= FIRST_YEAR).then_some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The year selected via `--year`, or the `AOC_YEAR` environment variable.
+    pub fn current() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Select this year for the current process and every process it spawns.
+    pub fn set_current(self) {
+        env::set_var("AOC_YEAR", self.to_string());
+    }
+
+    /// The selected year, if it keeps its files in its own directory. `None` if it uses the flat layout.
+    pub fn scoped() -> Option<Self> {
+        Self::current().filter(|year| year.has_own_dir())
+    }
+
+    /// Whether this year keeps its files in its own directory, i.e. `data/<year>` exists.
+    pub fn has_own_dir(self) -> bool {
+        Path::new("data").join(self.to_string()).is_dir()
+    }
+
+    /// Parses the year of a solution binary named like `2022-01`. Binaries of the flat layout (`01`) have no year.
+    pub const fn from_bin_name(name: &str) -> Option<Self> {
+        let bytes = name.as_bytes();
+
+        if bytes.len() != 7 || bytes[4] != b'-' {
+            return None;
+        }
+
+        let mut year = 0;
+        let mut i = 0;
+
+        while i < 4 {
+            if !bytes[i].is_ascii_digit() {
+                return None;
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        if year < FIRST_YEAR {
+            return None;
+        }
+
+        Some(Self(year))
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of advent, starting with {FIRST_YEAR}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2022".parse::<Year>().unwrap(), Year(2022));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("22".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn parses_bin_names() {
+        assert_eq!(Year::from_bin_name("2022-01"), Some(Year(2022)));
+        assert_eq!(Year::from_bin_name("01"), None);
+        assert_eq!(Year::from_bin_name("advent_of_code"), None);
+        assert_eq!(Year::from_bin_name("1999-01"), None);
+    }
+}